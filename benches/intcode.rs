@@ -0,0 +1,64 @@
+//! Compares running programs with and without `Intcode::enable_cache`, and compiled with
+//! `advent_of_code_2019::transpile`. Run with `cargo bench`.
+
+use advent_of_code_2019::compiled::day19;
+use advent_of_code_2019::{Intcode, Result};
+use std::time::{Duration, Instant};
+
+fn main() {
+    let day09 = Intcode::parse(include_str!("../input/2019/day09.txt"));
+    let day19 = Intcode::parse(include_str!("../input/2019/day19.txt"));
+
+    compare("day 9 part 2", 10, &day09, |code| {
+        assert_eq!(code.clone().add_input(2).run_all(), vec![46643]);
+    });
+    compare("day 19 part 1", 10, &day19, |code| {
+        let mut count = 0;
+        for y in 0..50 {
+            for x in 0..50 {
+                count += code.clone().add_input(x).add_input(y).run_expect_output();
+            }
+        }
+        assert_eq!(count, 110);
+    });
+
+    let compiled = measure(10, || {
+        let mut count = 0;
+        for y in 0..50 {
+            for x in 0..50 {
+                match day19::Machine::new().add_input(x).add_input(y).run() {
+                    Result::Output(o) => count += o,
+                    other => panic!("Expected output, got {:?}", other),
+                }
+            }
+        }
+        assert_eq!(count, 110);
+    });
+    println!("day 19 part 1: {:?} compiled", compiled);
+}
+
+fn compare(name: &str, iterations: u32, code: &Intcode, f: impl Fn(&Intcode)) {
+    let plain = measure(iterations, || f(code));
+
+    let mut cached = code.clone();
+    cached.enable_cache();
+    let cached = measure(iterations, || f(&cached));
+
+    println!(
+        "{}: {:?} without cache, {:?} with cache ({:.2}x)",
+        name,
+        plain,
+        cached,
+        plain.as_secs_f64() / cached.as_secs_f64()
+    );
+}
+
+fn measure(iterations: u32, f: impl Fn()) -> Duration {
+    // Warm up
+    f();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    start.elapsed() / iterations
+}
@@ -0,0 +1,113 @@
+#ifndef INTCODE_H
+#define INTCODE_H
+
+/* Generated with `cbindgen c -o c/intcode.h`, don't edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum IntcodeResult {
+  /**
+   * The value is the output.
+   */
+  INTCODE_RESULT_OUTPUT,
+  INTCODE_RESULT_NEED_INPUT,
+  INTCODE_RESULT_HALT,
+  /**
+   * The value is the instruction pointer of the instruction that failed: it overflowed, or the
+   * VM can't run it, e.g. because of an unknown opcode or a negative address.
+   */
+  INTCODE_RESULT_ERROR,
+  /**
+   * The value is the length of the cycle, see `intcode_detect_loops`.
+   */
+  INTCODE_RESULT_LOOPING,
+} IntcodeResult;
+
+/**
+ * A machine, only used through pointers.
+ */
+typedef struct IntcodeMachine IntcodeMachine;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a machine for the program in `prog` (`len` cells), free it with `intcode_free`.
+ *
+ * # Safety
+ *
+ * `prog` must point to `len` readable values.
+ */
+struct IntcodeMachine *intcode_new(const int64_t *prog, size_t len);
+
+/**
+ * # Safety
+ *
+ * `machine` must be a machine that wasn't freed.
+ */
+void intcode_add_input(struct IntcodeMachine *machine, int64_t input);
+
+/**
+ * Makes `intcode_run` return `Looping` instead of running forever without input or output.
+ *
+ * # Safety
+ *
+ * `machine` must be a machine that wasn't freed.
+ */
+void intcode_detect_loops(struct IntcodeMachine *machine);
+
+/**
+ * Runs until output, input is needed, halt or error. Stores the value of the result (see
+ * `IntcodeResult`) in `value` if it's not null. Instructions that the VM can't run are reported
+ * as errors too, running again after that fails again.
+ *
+ * # Safety
+ *
+ * `machine` must be a machine that wasn't freed, `value` null or writable.
+ */
+enum IntcodeResult intcode_run(struct IntcodeMachine *machine, int64_t *value);
+
+/**
+ * Returns the state of the machine as a buffer of `*len` bytes, free it with
+ * `intcode_free_buffer`.
+ *
+ * # Safety
+ *
+ * `machine` must be a machine that wasn't freed, `len` writable.
+ */
+uint8_t *intcode_snapshot(const struct IntcodeMachine *machine, size_t *len);
+
+/**
+ * Creates a machine from a snapshot (or a program in the binary format), returns null if it's
+ * invalid.
+ *
+ * # Safety
+ *
+ * `bytes` must point to `len` readable bytes.
+ */
+struct IntcodeMachine *intcode_restore(const uint8_t *bytes, size_t len);
+
+/**
+ * # Safety
+ *
+ * `machine` must be null or a machine that wasn't freed.
+ */
+void intcode_free(struct IntcodeMachine *machine);
+
+/**
+ * # Safety
+ *
+ * `buffer` must be null or returned by `intcode_snapshot` with `len`, and not freed yet.
+ */
+void intcode_free_buffer(uint8_t *buffer, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* INTCODE_H */
@@ -0,0 +1,39 @@
+/* Runs the quine from day 9 through the C API and checks that it outputs itself. */
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "intcode.h"
+
+int main(void) {
+    const int64_t prog[] = {109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99};
+    const size_t len = sizeof(prog) / sizeof(prog[0]);
+
+    IntcodeMachine *machine = intcode_new(prog, len);
+    int64_t outputs[sizeof(prog) / sizeof(prog[0])];
+    size_t count = 0;
+    int64_t value;
+    IntcodeResult result;
+    while ((result = intcode_run(machine, &value)) == INTCODE_RESULT_OUTPUT) {
+        if (count == len) {
+            fprintf(stderr, "Too many outputs\n");
+            return EXIT_FAILURE;
+        }
+        outputs[count++] = value;
+        printf(count == 1 ? "%lld" : ",%lld", (long long) value);
+    }
+    printf("\n");
+    intcode_free(machine);
+
+    if (result != INTCODE_RESULT_HALT || count != len) {
+        fprintf(stderr, "Expected halt after %zu outputs, got result %d after %zu\n", len, result, count);
+        return EXIT_FAILURE;
+    }
+    for (size_t i = 0; i < len; i++) {
+        if (outputs[i] != prog[i]) {
+            fprintf(stderr, "Output %zu is %lld, expected %lld\n", i, (long long) outputs[i], (long long) prog[i]);
+            return EXIT_FAILURE;
+        }
+    }
+    return EXIT_SUCCESS;
+}
@@ -0,0 +1,220 @@
+//! C API for embedding the VM, built as `libintcode`.
+//!
+//! The header is `c/intcode.h`, generated with `cbindgen c -o c/intcode.h`. `c/quine.c` is an
+//! example that runs the quine from day 9, build and run it with:
+//!
+//! ```sh
+//! cargo build --release -p intcode
+//! cc -Ic c/quine.c -Ltarget/release -lintcode -o target/quine
+//! LD_LIBRARY_PATH=target/release target/quine
+//! ```
+//!
+//! Snapshots use the format of [`advent_of_code_2019::binary`].
+
+use advent_of_code_2019::{Error, Intcode, Result};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice};
+
+/// A machine, only used through pointers.
+pub struct IntcodeMachine {
+    code: Intcode,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntcodeResult {
+    /// The value is the output.
+    Output,
+    NeedInput,
+    Halt,
+    /// The value is the instruction pointer of the instruction that failed: it overflowed, or the
+    /// VM can't run it, e.g. because of an unknown opcode or a negative address.
+    Error,
+    /// The value is the length of the cycle, see `intcode_detect_loops`.
+    Looping,
+}
+
+/// Creates a machine for the program in `prog` (`len` cells), free it with `intcode_free`.
+///
+/// # Safety
+///
+/// `prog` must point to `len` readable values.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_new(prog: *const i64, len: usize) -> *mut IntcodeMachine {
+    let prog = if len == 0 {
+        Vec::new()
+    } else {
+        slice::from_raw_parts(prog, len).to_vec()
+    };
+    Box::into_raw(Box::new(IntcodeMachine {
+        code: Intcode::new(prog),
+    }))
+}
+
+/// # Safety
+///
+/// `machine` must be a machine that wasn't freed.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_add_input(machine: *mut IntcodeMachine, input: i64) {
+    (*machine).code.add_input(input);
+}
+
+/// Makes `intcode_run` return `Looping` instead of running forever without input or output.
+///
+/// # Safety
+///
+/// `machine` must be a machine that wasn't freed.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_detect_loops(machine: *mut IntcodeMachine) {
+    (*machine).code.detect_loops();
+}
+
+/// Runs until output, input is needed, halt or error. Stores the value of the result (see
+/// `IntcodeResult`) in `value` if it's not null. Instructions that the VM can't run are reported
+/// as errors too, running again after that fails again.
+///
+/// # Safety
+///
+/// `machine` must be a machine that wasn't freed, `value` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_run(
+    machine: *mut IntcodeMachine,
+    value: *mut i64,
+) -> IntcodeResult {
+    let code = &mut (*machine).code;
+    // Panics must not unwind into C
+    let (result, v) = match panic::catch_unwind(AssertUnwindSafe(|| code.run())) {
+        Ok(Result::Output(o)) => (IntcodeResult::Output, o),
+        Ok(Result::NeedInput) => (IntcodeResult::NeedInput, 0),
+        Ok(Result::Halt) => (IntcodeResult::Halt, 0),
+        Ok(Result::Error(Error::Overflow { ip })) => (IntcodeResult::Error, ip),
+        Ok(Result::Looping(cycle)) => (IntcodeResult::Looping, cycle as i64),
+        Err(_) => (IntcodeResult::Error, code.ip()),
+    };
+    if !value.is_null() {
+        *value = v;
+    }
+    result
+}
+
+/// Returns the state of the machine as a buffer of `*len` bytes, free it with
+/// `intcode_free_buffer`.
+///
+/// # Safety
+///
+/// `machine` must be a machine that wasn't freed, `len` writable.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_snapshot(
+    machine: *const IntcodeMachine,
+    len: *mut usize,
+) -> *mut u8 {
+    let bytes = (*machine).code.to_binary().into_boxed_slice();
+    *len = bytes.len();
+    Box::into_raw(bytes) as *mut u8
+}
+
+/// Creates a machine from a snapshot (or a program in the binary format), returns null if it's
+/// invalid.
+///
+/// # Safety
+///
+/// `bytes` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_restore(bytes: *const u8, len: usize) -> *mut IntcodeMachine {
+    if bytes.is_null() {
+        return ptr::null_mut();
+    }
+    match Intcode::from_binary(slice::from_raw_parts(bytes, len)) {
+        Ok(code) => Box::into_raw(Box::new(IntcodeMachine { code })),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// # Safety
+///
+/// `machine` must be null or a machine that wasn't freed.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_free(machine: *mut IntcodeMachine) {
+    if !machine.is_null() {
+        drop(Box::from_raw(machine));
+    }
+}
+
+/// # Safety
+///
+/// `buffer` must be null or returned by `intcode_snapshot` with `len`, and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn intcode_free_buffer(buffer: *mut u8, len: usize) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer, len)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quine() {
+        let prog = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        unsafe {
+            let machine = intcode_new(prog.as_ptr(), prog.len());
+            let mut outputs = Vec::new();
+            let mut value = 0;
+            for _ in 0..8 {
+                assert_eq!(intcode_run(machine, &mut value), IntcodeResult::Output);
+                outputs.push(value);
+            }
+
+            // Continue halfway through on a restored snapshot
+            let mut len = 0;
+            let snapshot = intcode_snapshot(machine, &mut len);
+            intcode_free(machine);
+            let machine = intcode_restore(snapshot, len);
+            intcode_free_buffer(snapshot, len);
+            assert!(!machine.is_null());
+
+            let result = loop {
+                match intcode_run(machine, &mut value) {
+                    IntcodeResult::Output => outputs.push(value),
+                    result => break result,
+                }
+            };
+            intcode_free(machine);
+            assert_eq!(result, IntcodeResult::Halt);
+            assert_eq!(outputs, prog);
+        }
+    }
+
+    #[test]
+    fn test_panic() {
+        // Unknown opcode, and an output from a negative address
+        for (prog, ip) in &[
+            (vec![1101, 1, 1, 5, 42, 0], 4),
+            (vec![104, 1, 4, -1, 99], 2),
+        ] {
+            unsafe {
+                let machine = intcode_new(prog.as_ptr(), prog.len());
+                let mut value = 0;
+                let mut result = intcode_run(machine, &mut value);
+                if result == IntcodeResult::Output {
+                    result = intcode_run(machine, &mut value);
+                }
+                assert_eq!(result, IntcodeResult::Error);
+                assert_eq!(value, *ip);
+                assert_eq!(intcode_run(machine, ptr::null_mut()), IntcodeResult::Error);
+                intcode_free(machine);
+            }
+        }
+    }
+
+    #[test]
+    fn test_restore_invalid() {
+        let bytes = b"1,2,3";
+        unsafe {
+            assert!(intcode_restore(bytes.as_ptr(), bytes.len()).is_null());
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Sensitivity of a program's result to patched memory cells.
+//!
+//! Puzzles often patch a few cells before running (e.g. noun and verb in day 2) and look at a
+//! cell afterwards. If the result is an affine function of the patched cells, it can be found by
+//! running the program a few times, and then solved for a target value directly.
+
+use crate::Intcode;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+/// `constant + sum(coefficients[i] * values[i])`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Affine {
+    pub constant: i64,
+    pub coefficients: Vec<i64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Running with `values` didn't result in what the affine function predicted
+    NotAffine {
+        values: Vec<i64>,
+        expected: i64,
+        actual: i64,
+    },
+    /// Running with `values` didn't halt
+    NoHalt {
+        values: Vec<i64>,
+        result: crate::Result,
+    },
+    /// Running with `values` didn't halt within [`Sensitivity::max_steps`] steps
+    TooManySteps { values: Vec<i64> },
+    /// Computing the affine function for `values` overflowed
+    Overflow { values: Vec<i64> },
+    /// The range of the cell at `address` is empty
+    EmptyRange { address: usize },
+}
+
+impl Affine {
+    /// Returns `None` if the result overflows.
+    pub fn eval(&self, values: &[i64]) -> Option<i64> {
+        self.coefficients
+            .iter()
+            .zip(values)
+            .try_fold(self.constant, |acc, (c, v)| {
+                acc.checked_add(c.checked_mul(*v)?)
+            })
+    }
+
+    /// Finds values within `ranges` for which the function results in `target`.
+    ///
+    /// The cell with the biggest coefficient is solved for, all combinations of the other cells
+    /// are tried. Combinations for which the other cells overflow are skipped.
+    pub fn solve(&self, target: i64, ranges: &[RangeInclusive<i64>]) -> Option<Vec<i64>> {
+        let solved = (0..self.coefficients.len())
+            .filter(|&i| self.coefficients[i] != 0)
+            .max_by_key(|&i| self.coefficients[i].abs());
+        let solved = match solved {
+            Some(i) => i,
+            None if self.constant == target => {
+                return Some(ranges.iter().map(|r| *r.start()).collect());
+            }
+            None => return None,
+        };
+
+        let mut values: Vec<i64> = ranges.iter().map(|r| *r.start()).collect();
+        loop {
+            values[solved] = 0;
+            let remaining = self.eval(&values).and_then(|v| target.checked_sub(v));
+            let c = self.coefficients[solved];
+            if let Some(remaining) = remaining {
+                if remaining % c == 0 && ranges[solved].contains(&(remaining / c)) {
+                    values[solved] = remaining / c;
+                    return Some(values);
+                }
+            }
+
+            // Next combination of the other values
+            let mut i = 0;
+            loop {
+                if i == values.len() {
+                    return None;
+                }
+                if i != solved {
+                    if values[i] < *ranges[i].end() {
+                        values[i] += 1;
+                        break;
+                    }
+                    values[i] = *ranges[i].start();
+                }
+                i += 1;
+            }
+        }
+    }
+}
+
+pub struct Sensitivity {
+    code: Intcode,
+    output: usize,
+    cells: Vec<(usize, RangeInclusive<i64>)>,
+    checks: usize,
+    max_steps: usize,
+}
+
+impl Sensitivity {
+    /// Analyzes the value of the cell at `output` after `code` halted.
+    pub fn new(code: &Intcode, output: usize) -> Self {
+        Sensitivity {
+            code: code.clone(),
+            output,
+            cells: Vec::new(),
+            checks: 16,
+            max_steps: 10_000_000,
+        }
+    }
+
+    /// Adds a cell to patch. Only values within `range` are used, because e.g. the noun and verb
+    /// in day 2 are used as addresses.
+    pub fn cell(&mut self, address: usize, range: RangeInclusive<i64>) -> &mut Self {
+        self.cells.push((address, range));
+        self
+    }
+
+    /// Number of additional runs with pseudo-random values to check the function is affine.
+    pub fn checks(&mut self, checks: usize) -> &mut Self {
+        self.checks = checks;
+        self
+    }
+
+    /// Steps after which a run is stopped, as patching a cell can make the program loop forever.
+    /// 10 000 000 by default.
+    pub fn max_steps(&mut self, max_steps: usize) -> &mut Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn ranges(&self) -> Vec<RangeInclusive<i64>> {
+        self.cells.iter().map(|(_, range)| range.clone()).collect()
+    }
+
+    /// Finds the affine function by perturbing one cell at a time, then checks it against runs
+    /// with other values. Note that a function that passes the checks could still be non-affine
+    /// for values that weren't tried.
+    pub fn analyze(&self) -> Result<Affine, Error> {
+        if let Some((address, _)) = self.cells.iter().find(|(_, range)| range.is_empty()) {
+            return Err(Error::EmptyRange { address: *address });
+        }
+        let base: Vec<i64> = self.cells.iter().map(|(_, r)| *r.start()).collect();
+        let base_result = self.observe(&base)?;
+
+        let mut coefficients = Vec::new();
+        for (i, (_, range)) in self.cells.iter().enumerate() {
+            if range.start() == range.end() {
+                coefficients.push(0);
+                continue;
+            }
+            // Can't overflow, the range has more than one value
+            let mut values = base.clone();
+            values[i] += 1;
+            match self.observe(&values)?.checked_sub(base_result) {
+                Some(coefficient) => coefficients.push(coefficient),
+                None => return Err(Error::Overflow { values }),
+            }
+        }
+        let mut affine = Affine {
+            constant: 0,
+            coefficients,
+        };
+        // The constant is what's left of the result at the base values
+        affine.constant = match affine.eval(&base).and_then(|v| base_result.checked_sub(v)) {
+            Some(constant) => constant,
+            None => return Err(Error::Overflow { values: base }),
+        };
+
+        let mut random = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..self.checks {
+            let values: Vec<i64> = self
+                .cells
+                .iter()
+                .map(|(_, range)| {
+                    // xorshift
+                    random ^= random << 13;
+                    random ^= random >> 7;
+                    random ^= random << 17;
+                    let size = (*range.end() as i128 - *range.start() as i128 + 1) as u128;
+                    (*range.start() as i128 + (random as u128 % size) as i128) as i64
+                })
+                .collect();
+            let expected = match affine.eval(&values) {
+                Some(expected) => expected,
+                None => return Err(Error::Overflow { values }),
+            };
+            let actual = self.observe(&values)?;
+            if expected != actual {
+                return Err(Error::NotAffine {
+                    values,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(affine)
+    }
+
+    /// Analyzes, then solves for the values that result in `target`.
+    pub fn solve(&self, target: i64) -> Result<Option<Vec<i64>>, Error> {
+        Ok(self.analyze()?.solve(target, &self.ranges()))
+    }
+
+    fn observe(&self, values: &[i64]) -> Result<i64, Error> {
+        let mut code = self.code.clone();
+        for ((address, _), &value) in self.cells.iter().zip(values) {
+            if *address >= code.prog.len() {
+                code.prog.resize(*address + 1, 0);
+            }
+            code.prog[*address] = value;
+        }
+        for _ in 0..self.max_steps {
+            match code.step() {
+                None | Some(crate::Result::Output(_)) => {}
+                Some(crate::Result::Halt) => {
+                    return Ok(code.prog.get(self.output).cloned().unwrap_or(0));
+                }
+                Some(result) => {
+                    return Err(Error::NoHalt {
+                        values: values.to_vec(),
+                        result,
+                    })
+                }
+            }
+        }
+        Err(Error::TooManySteps {
+            values: values.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day2_input() {
+        let input = include_str!("../input/2019/day02.txt");
+        let code = Intcode::parse(input);
+        let mut sensitivity = Sensitivity::new(&code, 0);
+        sensitivity.cell(1, 0..=99).cell(2, 0..=99);
+
+        let affine = sensitivity.analyze().unwrap();
+        assert_eq!(affine.eval(&[12, 2]), Some(3_850_704));
+        assert_eq!(sensitivity.solve(19_690_720), Ok(Some(vec![67, 18])));
+    }
+
+    #[test]
+    fn test_not_affine() {
+        // Multiplies the two immediates
+        let code = Intcode::parse("1102,3,4,0,99");
+        let mut sensitivity = Sensitivity::new(&code, 0);
+        sensitivity.cell(1, 0..=10).cell(2, 0..=10);
+
+        match sensitivity.analyze() {
+            Err(Error::NotAffine {
+                values,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(expected, 0);
+                assert_eq!(actual, values[0] * values[1]);
+            }
+            other => panic!("Expected not affine, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_too_many_steps() {
+        // Jumps to the cell's value, which loops forever when it's 0
+        let code = Intcode::parse("1005,6,7,1105,1,0,0,99");
+        let mut sensitivity = Sensitivity::new(&code, 0);
+        sensitivity.cell(6, 0..=3).max_steps(1000);
+        assert_eq!(
+            sensitivity.analyze(),
+            Err(Error::TooManySteps { values: vec![0] })
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Multiplies the cell by 2^62, which the VM wraps for 2 but the function can't
+        let code = Intcode::parse("1002,5,4611686018427387904,0,99,0");
+        let mut sensitivity = Sensitivity::new(&code, 0);
+        sensitivity.cell(5, -2..=2);
+        match sensitivity.analyze() {
+            Err(Error::Overflow { values }) => assert_eq!(values, vec![2]),
+            other => panic!("Expected overflow, got {:?}", other),
+        }
+
+        let affine = Affine {
+            constant: 1,
+            coefficients: vec![i64::MAX],
+        };
+        assert_eq!(affine.eval(&[1]), None);
+        assert_eq!(affine.solve(0, &[-2..=2]), None);
+        assert_eq!(affine.solve(1 - i64::MAX, &[-2..=2]), Some(vec![-1]));
+    }
+
+    #[test]
+    fn test_empty_range_and_new_cell() {
+        let code = Intcode::parse("1001,10,1,0,99");
+        let mut sensitivity = Sensitivity::new(&code, 0);
+        sensitivity.cell(10, RangeInclusive::new(5, 3));
+        assert_eq!(
+            sensitivity.analyze(),
+            Err(Error::EmptyRange { address: 10 })
+        );
+
+        // The cell is beyond the program
+        let mut sensitivity = Sensitivity::new(&code, 0);
+        sensitivity.cell(10, 0..=9);
+        let affine = sensitivity.analyze().unwrap();
+        assert_eq!(affine.constant, 1);
+        assert_eq!(affine.coefficients, vec![1]);
+    }
+}
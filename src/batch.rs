@@ -0,0 +1,135 @@
+//! Running many clones of a program in parallel, e.g. probing every coordinate in day 19 or
+//! trying every phase setting permutation in day 7.
+//!
+//! Each item of a batch is a clone of the prototype with a list of inputs added. The items are
+//! handed out in order to a fixed number of threads, and the results are returned in order too.
+
+use crate::{Intcode, Word};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+pub struct Batch<W = i64> {
+    prototype: Intcode<W>,
+    threads: usize,
+}
+
+impl<W: Word + Send + Sync> Batch<W> {
+    /// A batch using as many threads as there are CPUs.
+    pub fn new(prototype: Intcode<W>) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Batch { prototype, threads }
+    }
+
+    pub fn threads(&mut self, threads: usize) -> &mut Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Runs each clone until it halts, returns the outputs of each.
+    pub fn run_all(&self, inputs: &[Vec<W>]) -> Vec<Vec<W>> {
+        self.map(inputs, |code| code.run_all())
+    }
+
+    /// Calls `f` with a clone of the prototype that has the inputs added, for each of `inputs`.
+    pub fn map<T, F>(&self, inputs: &[Vec<W>], f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&mut Intcode<W>) -> T + Sync,
+    {
+        let results = Mutex::new(Vec::with_capacity(inputs.len()));
+        self.work(inputs.len(), |i| {
+            let result = f(&mut self.clone_with(&inputs[i]));
+            results.lock().unwrap().push((i, result));
+            true
+        });
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Returns the first of `inputs` (in order) for which `f` returns `Some`, with its index.
+    /// Items after one that was found are skipped.
+    pub fn find<T, F>(&self, inputs: &[Vec<W>], f: F) -> Option<(usize, T)>
+    where
+        T: Send,
+        F: Fn(&mut Intcode<W>) -> Option<T> + Sync,
+    {
+        let found: Mutex<Option<(usize, T)>> = Mutex::new(None);
+        self.work(inputs.len(), |i| {
+            if let Some(result) = f(&mut self.clone_with(&inputs[i])) {
+                let mut found = found.lock().unwrap();
+                // Another thread could have found an earlier one in the meantime
+                if found.as_ref().is_none_or(|(j, _)| i < *j) {
+                    *found = Some((i, result));
+                }
+                return false;
+            }
+            true
+        });
+        found.into_inner().unwrap()
+    }
+
+    fn clone_with(&self, inputs: &[W]) -> Intcode<W> {
+        let mut code = self.prototype.clone();
+        for input in inputs {
+            code.add_input(input.clone());
+        }
+        code
+    }
+
+    /// Calls `f` for each index in `0..count` on the threads. When `f` returns false for an
+    /// index, the indexes after it aren't started anymore.
+    fn work(&self, count: usize, f: impl Fn(usize) -> bool + Sync) {
+        let next = AtomicUsize::new(0);
+        let end = AtomicUsize::new(count);
+        thread::scope(|scope| {
+            for _ in 0..self.threads.min(count) {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    if i >= end.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if !f(i) {
+                        end.fetch_min(i, Ordering::SeqCst);
+                    }
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_all_in_order() {
+        let code = Intcode::parse(include_str!("../input/2019/day19.txt"));
+        let inputs: Vec<Vec<i64>> = (0..50)
+            .flat_map(|y| (0..50).map(move |x| vec![x, y]))
+            .collect();
+        let results = Batch::new(code.clone()).threads(4).run_all(&inputs);
+        assert_eq!(results.len(), 2500);
+        assert_eq!(results.iter().map(|r| r[0]).sum::<i64>(), 110);
+        for (input, result) in inputs.iter().zip(&results).step_by(97) {
+            let mut code = code.clone();
+            code.add_input(input[0]).add_input(input[1]);
+            assert_eq!(&code.run_all(), result);
+        }
+    }
+
+    #[test]
+    fn test_find_first() {
+        // Outputs the input times 2
+        let code = Intcode::parse("3,9,1002,9,2,9,4,9,99,0");
+        let inputs: Vec<Vec<i64>> = (0..1000).map(|i| vec![i]).collect();
+        let runs = AtomicUsize::new(0);
+        let found = Batch::new(code).threads(4).find(&inputs, |code| {
+            runs.fetch_add(1, Ordering::SeqCst);
+            Some(code.run_expect_output()).filter(|&o| o >= 200 && o % 3 == 0)
+        });
+        assert_eq!(found, Some((102, 204)));
+        assert!(runs.load(Ordering::SeqCst) < 1000);
+    }
+}
@@ -0,0 +1,20 @@
+//! Compiles a program in the language of `advent_of_code_2019::compiler` and prints the Intcode.
+//!
+//! Usage: `cargo run --bin compile <source>`
+
+use advent_of_code_2019::compiler::compile;
+use std::{env, fs};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: {} <source>", args[0]);
+        std::process::exit(1);
+    }
+
+    let source = fs::read_to_string(&args[1])
+        .unwrap_or_else(|e| panic!("Error reading {:?}: {}", args[1], e));
+    let prog = compile(&source).unwrap_or_else(|e| panic!("{}: {}", args[1], e));
+    let cells: Vec<String> = prog.iter().map(|v| v.to_string()).collect();
+    println!("{}", cells.join(","));
+}
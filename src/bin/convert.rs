@@ -0,0 +1,29 @@
+//! Converts an Intcode program between text and the binary format of
+//! `advent_of_code_2019::binary`, in whichever direction applies to the input.
+//!
+//! Usage: `cargo run --bin convert <input> <output>`
+
+use advent_of_code_2019::binary;
+use advent_of_code_2019::Intcode;
+use std::{env, fs};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        eprintln!("Usage: {} <input> <output>", args[0]);
+        std::process::exit(1);
+    }
+
+    let input = fs::read(&args[1]).unwrap_or_else(|e| panic!("Error reading {:?}: {}", args[1], e));
+    let output = if binary::is_binary(&input) {
+        let program = binary::Program::decode(&input)
+            .unwrap_or_else(|e| panic!("Error decoding {:?}: {}", args[1], e));
+        let cells: Vec<String> = program.memory.iter().map(|v| v.to_string()).collect();
+        (cells.join(",") + "\n").into_bytes()
+    } else {
+        let text = String::from_utf8(input)
+            .unwrap_or_else(|_| panic!("{:?} is neither binary nor text", args[1]));
+        Intcode::parse(&text).to_binary()
+    };
+    fs::write(&args[2], output).unwrap_or_else(|e| panic!("Error writing {:?}: {}", args[2], e));
+}
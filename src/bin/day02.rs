@@ -63,6 +63,8 @@ fn calculate(mut prog: Vec<usize>) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "std")]
+    use advent_of_code_2019::fuzz::{self, differential, Config, Implementation, Outcome};
 
     #[test]
     fn test_examples() {
@@ -75,4 +77,37 @@ mod tests {
                 30, 40, 50]),
             3500);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_differential() {
+        let day02 = |prog: &[i64], _input: i64| {
+            let prog = prog.iter().map(|&v| v as usize).collect();
+            Outcome {
+                memory: Some(vec![calculate(prog) as i64]),
+                ..Outcome::default()
+            }
+        };
+        let implementations = [
+            Implementation {
+                name: "day02",
+                opcodes: &[1, 2],
+                modes: false,
+                run: &day02,
+            },
+            Implementation {
+                name: "intcode",
+                opcodes: &[1, 2, 3, 4, 5, 6, 7, 8, 9],
+                modes: true,
+                run: &fuzz::intcode,
+            },
+        ];
+        let config = Config {
+            negative: false,
+            ..Config::default()
+        };
+        if let Some(failure) = differential(&implementations, config) {
+            panic!("{:?}", failure);
+        }
+    }
 }
@@ -121,6 +121,8 @@ fn calculate(mut prog: Vec<i32>, input: i32) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "std")]
+    use advent_of_code_2019::fuzz::{self, differential, Config, Implementation, Outcome};
 
     #[test]
     fn test_examples() {
@@ -130,4 +132,33 @@ mod tests {
         assert_eq!(calculate(prog.clone(), 8), 1000);
         assert_eq!(calculate(prog.clone(), 9), 1001);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_differential() {
+        let day05 = |prog: &[i64], input: i64| {
+            let prog = prog.iter().map(|&v| v as i32).collect();
+            Outcome {
+                last_output: Some(calculate(prog, input as i32) as i64),
+                ..Outcome::default()
+            }
+        };
+        let implementations = [
+            Implementation {
+                name: "day05",
+                opcodes: &[1, 2, 3, 4, 5, 6, 7, 8],
+                modes: true,
+                run: &day05,
+            },
+            Implementation {
+                name: "intcode",
+                opcodes: &[1, 2, 3, 4, 5, 6, 7, 8, 9],
+                modes: true,
+                run: &fuzz::intcode,
+            },
+        ];
+        if let Some(failure) = differential(&implementations, Config::default()) {
+            panic!("{:?}", failure);
+        }
+    }
 }
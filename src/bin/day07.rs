@@ -108,6 +108,8 @@ fn score_part2(prog: &str, sequence: Vec<i64>) -> i64 {
                 Result::Output(o) => signal = o,
                 Result::Halt => return signal,
                 Result::NeedInput => panic!("Unexpected input needed"),
+                Result::Error(e) => panic!("{}", e),
+                Result::Looping(cycle) => panic!("Looping with a cycle of {} instructions", cycle),
             }
         }
     }
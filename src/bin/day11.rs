@@ -1,5 +1,6 @@
 //! https://adventofcode.com/2019/day/11
 
+use advent_of_code_2019::frame::FrameError;
 use advent_of_code_2019::{Intcode, Result};
 use std::collections::HashMap;
 
@@ -40,30 +41,16 @@ fn paint(mut code: Intcode, starting_color: i64) -> HashMap<(i32, i32), i64> {
     loop {
         code.add_input(*map.get(&(x, y)).unwrap_or(&0));
 
-        match code.run() {
-            Result::Output(new_color) => {
-                map.insert((x, y), new_color);
-            }
-            Result::Halt => {
-                break;
-            }
-            Result::NeedInput => {
-                panic!("Unexpected input needed");
-            }
-        }
-
-        match code.run() {
-            Result::Output(turn) => match turn {
-                0 => direction = if direction == 0 { 3 } else { direction - 1 },
-                1 => direction = (direction + 1) % 4,
-                other => panic!("Unknown direction output {}", other),
-            },
-            Result::Halt => {
-                break;
-            }
-            Result::NeedInput => {
-                panic!("Unexpected input needed");
-            }
+        let (new_color, turn) = match code.read_frame() {
+            Ok(frame) => frame,
+            Err(FrameError::Stopped(Result::Halt)) => break,
+            Err(e) => panic!("{}", e),
+        };
+        map.insert((x, y), new_color);
+        match turn {
+            0 => direction = if direction == 0 { 3 } else { direction - 1 },
+            1 => direction = (direction + 1) % 4,
+            other => panic!("Unknown direction output {}", other),
         }
 
         match direction {
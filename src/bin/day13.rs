@@ -4,6 +4,7 @@ use cursive::event::{Event, EventResult, Key};
 use cursive::traits::*;
 use cursive::{theme, Cursive, Printer};
 
+use advent_of_code_2019::frame::{FrameError, Screen, Tile};
 use advent_of_code_2019::{Intcode, Result};
 
 fn main() {
@@ -111,30 +112,10 @@ impl View for Game {
 
 impl Game {
     fn run_until_input(&mut self) {
-        loop {
-            let x = match self.code.run() {
-                Result::Output(x) => x,
-                _ => {
-                    return;
-                }
-            };
-
-            let y = match self.code.run() {
-                Result::Output(y) => y,
-                _ => return,
-            };
-
-            let t = match self.code.run() {
-                Result::Output(t) => t,
-                _ => {
-                    return;
-                }
-            };
-
-            if x == -1 && y == 0 {
-                self.score = t;
-            } else {
-                self.map[y as usize][x as usize] = t;
+        for screen in self.code.frames() {
+            match screen.unwrap_or_else(|e| panic!("{}", e)) {
+                Screen::Score(score) => self.score = score,
+                Screen::Tile(Tile { x, y, id }) => self.map[y as usize][x as usize] = id,
             }
         }
     }
@@ -153,9 +134,9 @@ fn play_game() -> (usize, i64) {
     let mut finishing = false;
     let mut all_blocks = 0;
     loop {
-        let x = match code.run() {
-            Result::Output(x) => x,
-            Result::NeedInput => {
+        let screen = match code.read_frame() {
+            Ok(screen) => screen,
+            Err(FrameError::Stopped(Result::NeedInput)) => {
                 if ball.0 < paddle.0 {
                     code.add_input(-1);
                 } else if ball.0 > paddle.0 {
@@ -165,37 +146,27 @@ fn play_game() -> (usize, i64) {
                 }
                 continue;
             }
-            Result::Halt => {
+            Err(FrameError::Stopped(Result::Halt)) => {
                 panic!("Unexpected halt before game finished");
             }
-        };
-
-        let y = match code.run() {
-            Result::Output(y) => y,
-            other => {
-                panic!("Unexpected result {:?}", other);
-            }
-        };
-
-        let t = match code.run() {
-            Result::Output(t) => t,
-            other => {
-                panic!("Unexpected result {:?}", other);
+            Err(e) => {
+                panic!("{}", e);
             }
         };
 
-        if x == -1 && y == 0 {
-            score = t;
-        } else {
-            map[y as usize][x as usize] = t;
-            match t {
-                3 => {
-                    paddle = (x, y);
-                }
-                4 => {
-                    ball = (x, y);
+        match screen {
+            Screen::Score(s) => score = s,
+            Screen::Tile(Tile { x, y, id }) => {
+                map[y as usize][x as usize] = id;
+                match id {
+                    3 => {
+                        paddle = (x, y);
+                    }
+                    4 => {
+                        ball = (x, y);
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
         }
 
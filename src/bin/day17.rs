@@ -15,7 +15,7 @@ fn main() {
 }
 
 fn print(mut code: Intcode) {
-    while let Result::Output(output) = code.run() {
+    for output in code.outputs() {
         print!("{}", char::from(output as u8));
     }
 }
@@ -78,7 +78,7 @@ n
     }
 
     let mut dust = 0;
-    while let Result::Output(output) = code.run() {
+    for output in code.outputs() {
         if output <= 127 {
             print!("{}", char::from(output as u8));
         }
@@ -92,7 +92,7 @@ fn map(mut code: Intcode) -> (Point, HashSet<Point>) {
     let mut x = 0;
     let mut y = 0;
     let mut robot = None;
-    while let Result::Output(output) = code.run() {
+    for output in code.outputs() {
         let c = char::from(output as u8);
         match c {
             '#' => {
@@ -194,7 +194,7 @@ mod tests {
         let s = include_str!("../../input/2019/day17.txt").trim();
         let code = Intcode::parse(s);
 
-        assert_eq!(solve1(code), 5620);
+        assert_eq!(solve1(code.clone()), 5620);
         assert_eq!(solve2(code), 768115);
     }
 }
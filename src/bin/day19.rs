@@ -1,7 +1,8 @@
 //! https://adventofcode.com/2019/day/19
 
+use advent_of_code_2019::batch::Batch;
+use advent_of_code_2019::oracle::Oracle;
 use advent_of_code_2019::*;
-use std::collections::HashMap;
 
 fn main() {
     let input = include_str!("../../input/2019/day19.txt").trim();
@@ -13,13 +14,14 @@ fn main() {
 }
 
 fn solve1(code: Intcode) -> usize {
+    let inputs: Vec<Vec<i64>> = (0..50)
+        .flat_map(|y| (0..50).map(move |x| vec![x, y]))
+        .collect();
+    let outputs = Batch::new(code).map(&inputs, |code| code.run_expect_output());
     let mut count = 0;
-    for y in 0..50 {
-        for x in 0..50 {
-            let mut code = code.clone();
-            code.add_input(x);
-            code.add_input(y);
-            if code.run_expect_output() == 1 {
+    for row in outputs.chunks(50) {
+        for &output in row {
+            if output == 1 {
                 count += 1;
                 print!("#");
             } else {
@@ -105,36 +107,22 @@ fn solve2(code: Intcode) -> (i64, i64) {
 }
 
 struct Drone {
-    code: Intcode,
-    map: HashMap<(i64, i64), bool>,
-    checks: usize,
+    oracle: Oracle<bool>,
 }
 
 impl Drone {
     fn new(code: Intcode) -> Drone {
         Drone {
-            code,
-            map: HashMap::new(),
-            checks: 0,
+            oracle: Oracle::new(code, |outputs| outputs == [1]),
         }
     }
 
     fn check(&mut self, point: (i64, i64)) -> bool {
-        if let Some(&result) = self.map.get(&point) {
-            result
-        } else {
-            let mut code = self.code.clone();
-            code.add_input(point.0);
-            code.add_input(point.1);
-            let result = code.run_expect_output() == 1;
-            self.checks += 1;
-            self.map.insert(point, result);
-            result
-        }
+        self.oracle.query(&[point.0, point.1])
     }
 
     fn checks(&self) -> usize {
-        self.checks
+        self.oracle.misses()
     }
 
     fn get_width(&mut self, (x, y): (i64, i64)) -> i64 {
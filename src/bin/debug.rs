@@ -0,0 +1,26 @@
+//! Runs a debug server for an Intcode program, see `advent_of_code_2019::debug`.
+//!
+//! Usage: `cargo run --bin debug <program> [port]`
+
+use advent_of_code_2019::debug::Debugger;
+use advent_of_code_2019::Intcode;
+use std::net::TcpListener;
+use std::{env, fs};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: {} <program> [port]", args[0]);
+        std::process::exit(1);
+    }
+
+    let input = fs::read_to_string(&args[1])
+        .unwrap_or_else(|e| panic!("Error reading {:?}: {}", args[1], e));
+    let port = args.get(2).map(|s| s.as_str()).unwrap_or("1234");
+    let listener = TcpListener::bind(format!("127.0.0.1:{}", port))
+        .unwrap_or_else(|e| panic!("Error binding port {}: {}", port, e));
+    eprintln!("Listening on {}", listener.local_addr().unwrap());
+
+    let mut debugger = Debugger::new(Intcode::parse(&input));
+    debugger.serve(&listener).unwrap();
+}
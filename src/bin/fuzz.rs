@@ -0,0 +1,43 @@
+//! Fuzzes the inputs of an Intcode program until interrupted, see
+//! `advent_of_code_2019::coverage`. The corpus and crashes are stored in a directory, so that a
+//! later run continues where this one stopped.
+//!
+//! Usage: `cargo run --release --bin fuzz <program> <corpus dir> [seed]`
+
+use advent_of_code_2019::coverage::Fuzzer;
+use advent_of_code_2019::Intcode;
+use std::{env, fs};
+
+const BATCH: usize = 10_000;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        eprintln!("Usage: {} <program> <corpus dir> [seed]", args[0]);
+        std::process::exit(1);
+    }
+
+    let input = fs::read_to_string(&args[1])
+        .unwrap_or_else(|e| panic!("Error reading {:?}: {}", args[1], e));
+    let seed = args
+        .get(3)
+        .map(|s| s.parse().unwrap_or_else(|_| panic!("Invalid seed {:?}", s)))
+        .unwrap_or(1);
+    let mut fuzzer = Fuzzer::new(Intcode::parse(&input), seed);
+    fuzzer
+        .corpus_dir(&args[2])
+        .unwrap_or_else(|e| panic!("Error loading corpus from {:?}: {}", args[2], e));
+
+    for batch in 1.. {
+        for crash in fuzzer.fuzz(BATCH) {
+            println!("{} with inputs {:?}", crash.fault, crash.inputs);
+        }
+        println!(
+            "{} runs, corpus {}, {} addresses, {} edges",
+            batch * BATCH,
+            fuzzer.corpus().len(),
+            fuzzer.addresses(),
+            fuzzer.edges()
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Records execution traces and finds where two of them diverge, see
+//! `advent_of_code_2019::trace`.
+//!
+//! Usage:
+//!
+//! * `cargo run --bin trace record <program> [input...]` prints the trace of a program
+//! * `cargo run --bin trace diff <trace> <trace>` prints the first divergence, if any
+
+use advent_of_code_2019::trace::{self, diff, record};
+use advent_of_code_2019::Intcode;
+use std::{env, fs};
+
+const LIMIT: usize = 10_000_000;
+const CONTEXT: usize = 5;
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("Error reading {:?}: {}", path, e))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(|s| s.as_str()) {
+        Some("record") if args.len() >= 3 => {
+            let mut code = Intcode::parse(&read(&args[2]));
+            for input in &args[3..] {
+                code.add_input(
+                    input
+                        .parse()
+                        .unwrap_or_else(|_| panic!("Invalid input {:?}", input)),
+                );
+            }
+            for step in record(code, LIMIT) {
+                println!("{}", step);
+            }
+        }
+        Some("diff") if args.len() == 4 => {
+            let parse = |path: &str| {
+                trace::parse(&read(path)).unwrap_or_else(|e| panic!("{}: {}", path, e))
+            };
+            let (a, b) = (parse(&args[2]), parse(&args[3]));
+            match diff(&a, &b, CONTEXT) {
+                Some(divergence) => {
+                    print!("{}", divergence);
+                    std::process::exit(1);
+                }
+                None => println!("Traces are the same ({} steps)", a.len()),
+            }
+        }
+        _ => {
+            eprintln!("Usage: {} record <program> [input...]", args[0]);
+            eprintln!("       {} diff <trace> <trace>", args[0]);
+            std::process::exit(2);
+        }
+    }
+}
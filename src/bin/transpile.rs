@@ -0,0 +1,24 @@
+//! Prints a Rust module for an Intcode program, see `advent_of_code_2019::transpile`.
+//!
+//! Usage: `cargo run --bin transpile <program> [crate]`
+
+use advent_of_code_2019::transpile::transpile;
+use advent_of_code_2019::Intcode;
+use std::{env, fs};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: {} <program> [crate]", args[0]);
+        std::process::exit(1);
+    }
+
+    let input = fs::read_to_string(&args[1])
+        .unwrap_or_else(|e| panic!("Error reading {:?}: {}", args[1], e));
+    let code = Intcode::parse(&input);
+    let krate = args
+        .get(2)
+        .map(|s| s.as_str())
+        .unwrap_or("advent_of_code_2019");
+    print!("{}", transpile(&code.prog, krate));
+}
@@ -0,0 +1,328 @@
+//! A compact binary format for Intcode programs, as an alternative to comma-separated text.
+//!
+//! Layout:
+//!
+//! * Magic `ICB\0`, format version (1 byte), word width in bits (1 byte, always 64 for now)
+//! * CRC-32 of everything after the header (4 bytes, little-endian)
+//! * Memory: number of cells, then each cell as a zigzag varint
+//! * Optional sections, each a tag byte, a varint length and the contents:
+//!   * [`SYMBOLS`]: number of symbols, then for each the address and the UTF-8 name (with length)
+//!   * [`SNAPSHOT`]: instruction pointer, relative base, number of inputs and the inputs
+//!
+//! Readers skip sections they don't know, so that new ones can be added without a new version.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+pub const MAGIC: &[u8; 4] = b"ICB\0";
+pub const VERSION: u8 = 1;
+pub const WIDTH: u8 = 64;
+
+pub const SYMBOLS: u8 = 1;
+pub const SNAPSHOT: u8 = 2;
+
+const HEADER: usize = 4 + 1 + 1 + 4;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Program {
+    pub memory: Vec<i64>,
+    /// Names for addresses, e.g. from an assembler
+    pub symbols: Vec<(i64, String)>,
+    /// State of a machine that was already running
+    pub snapshot: Option<Snapshot>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Snapshot {
+    pub ip: i64,
+    pub relative_base: i64,
+    pub inputs: Vec<i64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    Magic,
+    Version(u8),
+    Width(u8),
+    Checksum { expected: u32, actual: u32 },
+    Truncated,
+    Invalid(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Magic => write!(f, "Not an Intcode binary"),
+            Error::Version(v) => write!(f, "Unsupported version {}", v),
+            Error::Width(w) => write!(f, "Unsupported word width {}", w),
+            Error::Checksum { expected, actual } => write!(
+                f,
+                "Checksum mismatch, expected {:08x} but was {:08x}",
+                expected, actual
+            ),
+            Error::Truncated => write!(f, "Unexpected end of data"),
+            Error::Invalid(what) => write!(f, "Invalid {}", what),
+        }
+    }
+}
+
+impl Program {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        write_varint(&mut body, self.memory.len() as u64);
+        for &value in &self.memory {
+            write_signed(&mut body, value);
+        }
+
+        if !self.symbols.is_empty() {
+            let mut section = Vec::new();
+            write_varint(&mut section, self.symbols.len() as u64);
+            for (address, name) in &self.symbols {
+                write_signed(&mut section, *address);
+                write_varint(&mut section, name.len() as u64);
+                section.extend(name.as_bytes());
+            }
+            write_section(&mut body, SYMBOLS, &section);
+        }
+        if let Some(snapshot) = &self.snapshot {
+            let mut section = Vec::new();
+            write_signed(&mut section, snapshot.ip);
+            write_signed(&mut section, snapshot.relative_base);
+            write_varint(&mut section, snapshot.inputs.len() as u64);
+            for &input in &snapshot.inputs {
+                write_signed(&mut section, input);
+            }
+            write_section(&mut body, SNAPSHOT, &section);
+        }
+
+        let mut bytes = Vec::with_capacity(HEADER + body.len());
+        bytes.extend(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(WIDTH);
+        bytes.extend(&crc32(&body).to_le_bytes());
+        bytes.extend(body);
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Program, Error> {
+        if !is_binary(bytes) {
+            return Err(Error::Magic);
+        }
+        if bytes.len() < HEADER {
+            return Err(Error::Truncated);
+        }
+        if bytes[4] != VERSION {
+            return Err(Error::Version(bytes[4]));
+        }
+        if bytes[5] != WIDTH {
+            return Err(Error::Width(bytes[5]));
+        }
+        let expected = u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]);
+        let body = &bytes[HEADER..];
+        let actual = crc32(body);
+        if expected != actual {
+            return Err(Error::Checksum { expected, actual });
+        }
+
+        let mut reader = Reader { bytes: body };
+        let mut program = Program::default();
+        let count = reader.len()?;
+        for _ in 0..count {
+            program.memory.push(reader.signed()?);
+        }
+        while !reader.bytes.is_empty() {
+            let tag = reader.byte()?;
+            let len = reader.len()?;
+            let mut section = Reader {
+                bytes: reader.take(len)?,
+            };
+            match tag {
+                SYMBOLS => {
+                    for _ in 0..section.len()? {
+                        let address = section.signed()?;
+                        let len = section.len()?;
+                        let name = String::from_utf8(section.take(len)?.to_vec())
+                            .map_err(|_| Error::Invalid("symbol name"))?;
+                        program.symbols.push((address, name));
+                    }
+                }
+                SNAPSHOT => {
+                    let ip = section.signed()?;
+                    let relative_base = section.signed()?;
+                    let inputs = (0..section.len()?)
+                        .map(|_| section.signed())
+                        .collect::<Result<_, _>>()?;
+                    program.snapshot = Some(Snapshot {
+                        ip,
+                        relative_base,
+                        inputs,
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(program)
+    }
+}
+
+/// Whether `bytes` start like a binary program (as opposed to text).
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+fn write_section(out: &mut Vec<u8>, tag: u8, section: &[u8]) {
+    out.push(tag);
+    write_varint(out, section.len() as u64);
+    out.extend(section);
+}
+
+/// LEB128: 7 bits per byte, high bit set if more bytes follow.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Zigzag, so that small negative numbers are small too.
+fn write_signed(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, Error> {
+        let (&byte, rest) = self.bytes.split_first().ok_or(Error::Truncated)?;
+        self.bytes = rest;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if len > self.bytes.len() {
+            return Err(Error::Truncated);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::Invalid("varint"))
+    }
+
+    fn signed(&mut self) -> Result<i64, Error> {
+        let value = self.varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// A count or length, which can't be more than the remaining bytes.
+    fn len(&mut self) -> Result<usize, Error> {
+        let len = self.varint()?;
+        if len > self.bytes.len() as u64 {
+            return Err(Error::Truncated);
+        }
+        Ok(len as usize)
+    }
+}
+
+/// CRC-32 as in zlib and PNG.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Intcode, Result};
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let code = Intcode::parse(include_str!("../input/2019/day09.txt"));
+        let bytes = code.to_binary();
+        assert!(bytes.len() < include_str!("../input/2019/day09.txt").len() / 2);
+        let decoded = Intcode::from_binary(&bytes).unwrap();
+        assert_eq!(decoded.prog, code.prog);
+        assert_eq!(decoded.clone().add_input(1).run_all(), vec![2955820355]);
+
+        let program = Program {
+            memory: vec![0, -1, i64::MIN, i64::MAX, 1 << 40],
+            symbols: vec![(3, "main".to_string()), (-1, "ünïcode".to_string())],
+            snapshot: Some(Snapshot {
+                ip: 2,
+                relative_base: -7,
+                inputs: vec![5, -5],
+            }),
+        };
+        assert_eq!(Program::decode(&program.encode()), Ok(program));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        // Outputs each input times 2, until the input is 0
+        let mut code = Intcode::parse("3,15,1006,15,14,1002,15,2,16,4,16,1105,1,0,99,0,0");
+        code.add_input(1).add_input(2).add_input(3).add_input(0);
+        assert_eq!(code.run(), Result::Output(2));
+
+        let mut restored = Intcode::from_binary(&code.to_binary()).unwrap();
+        assert_eq!(restored.ip(), code.ip());
+        assert_eq!(restored.run_all(), vec![4, 6]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let bytes = Intcode::parse("1,0,0,0,99").to_binary();
+        assert_eq!(Program::decode(b"1,0,0,0,99"), Err(Error::Magic));
+        assert_eq!(Program::decode(&bytes[..8]), Err(Error::Truncated));
+
+        let mut corrupted = bytes.clone();
+        corrupted[HEADER + 1] ^= 1;
+        assert!(matches!(
+            Program::decode(&corrupted),
+            Err(Error::Checksum { .. })
+        ));
+
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert_eq!(Program::decode(&version), Err(Error::Version(2)));
+
+        // Unknown sections are skipped
+        let mut extended = bytes[HEADER..].to_vec();
+        write_section(&mut extended, 99, &[1, 2, 3]);
+        let mut bytes = bytes[..HEADER].to_vec();
+        bytes[6..10].copy_from_slice(&crc32(&extended).to_le_bytes());
+        bytes.extend(extended);
+        assert_eq!(
+            Intcode::from_binary(&bytes).unwrap().prog,
+            vec![1, 0, 0, 0, 99]
+        );
+    }
+}
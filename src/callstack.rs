@@ -0,0 +1,203 @@
+//! Reconstructing the call stack of programs that implement functions with the relative base,
+//! so that tools can report by function instead of by address.
+//!
+//! Intcode has no call instruction. Programs (the puzzle inputs as well as
+//! [`crate::compiler`]) write the return address to the stack, adjust the relative base and
+//! jump to the function. The function returns by restoring the relative base and jumping to the
+//! address on the stack, e.g. with `2106,0,0`. [`CallStack`] spots these patterns while the
+//! program runs:
+//!
+//! * A call is a taken jump from `ip` after the address of the next instruction (`ip + 3`) was
+//!   written to memory, since the previous jump.
+//! * A return is a taken jump with a target that isn't immediate, to the return address of a
+//!   frame on the stack. It also pops the frames above, in case they didn't return normally.
+
+use crate::{Instruction, Intcode, Result, Word};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    /// Address of the function, which is the target of the call
+    pub function: i64,
+    /// Address of the jump that called it
+    pub call_site: i64,
+    pub return_address: i64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CallStack {
+    frames: Vec<Frame>,
+    /// Values written since the last jump
+    written: Vec<i64>,
+}
+
+impl CallStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Frames of the functions that were called and didn't return yet, the innermost last.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// The function the machine is in, or 0 (the start of the program) if it's not in one.
+    pub fn function(&self) -> i64 {
+        self.frames.last().map_or(0, |frame| frame.function)
+    }
+
+    /// Runs one instruction with [`Intcode::step`] and updates the stack.
+    pub fn step<W: Word>(&mut self, code: &mut Intcode<W>) -> Option<Result<W>> {
+        let ip = code.ip();
+        let instruction = cell(code, ip).map(Instruction::decode);
+        let result = code.step();
+        let stopped = matches!(
+            result,
+            Some(Result::NeedInput) | Some(Result::Error(_)) | Some(Result::Looping(_))
+        );
+        if let (Some(Instruction { opcode, modes }), false) = (instruction, stopped) {
+            match opcode {
+                1 | 2 | 7 | 8 => self.wrote(code, ip + 3, modes[2]),
+                3 => self.wrote(code, ip + 1, modes[0]),
+                5 | 6 => self.jumped(ip, modes[1], code.ip()),
+                _ => {}
+            }
+        }
+        result
+    }
+
+    fn wrote<W: Word>(&mut self, code: &Intcode<W>, param: i64, mode: i64) {
+        let address = match (cell(code, param), mode) {
+            (Some(address), 0) => address,
+            (Some(offset), 2) => code.relative_base() + offset,
+            _ => return,
+        };
+        if let Some(value) = cell(code, address) {
+            self.written.push(value);
+        }
+    }
+
+    fn jumped(&mut self, ip: i64, target_mode: i64, target: i64) {
+        let next = ip + 3;
+        if target != next {
+            let returned = self
+                .frames
+                .iter()
+                .rposition(|frame| frame.return_address == target);
+            match returned {
+                Some(i) if target_mode != 1 => self.frames.truncate(i),
+                _ if self.written.contains(&next) => self.frames.push(Frame {
+                    function: target,
+                    call_site: ip,
+                    return_address: next,
+                }),
+                _ => {}
+            }
+        }
+        self.written.clear();
+    }
+}
+
+fn cell<W: Word>(code: &Intcode<W>, address: i64) -> Option<i64> {
+    if address < 0 {
+        return None;
+    }
+    code.prog
+        .get(address as usize)
+        .and_then(|value| value.to_i64())
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Profile {
+    pub calls: u64,
+    /// Instructions run in the function itself, not in the ones it called
+    pub instructions: u64,
+}
+
+/// Runs `code` until it halts, needs input or fails, and counts calls and instructions per
+/// function. Outputs are dropped. Instructions outside of functions count for function 0.
+pub fn profile<W: Word>(code: &mut Intcode<W>) -> BTreeMap<i64, Profile> {
+    let mut stack = CallStack::new();
+    let mut profiles: BTreeMap<i64, Profile> = BTreeMap::new();
+    loop {
+        profiles.entry(stack.function()).or_default().instructions += 1;
+        let depth = stack.frames().len();
+        match stack.step(code) {
+            None | Some(Result::Output(_)) => {}
+            Some(_) => return profiles,
+        }
+        if stack.frames().len() > depth {
+            profiles.entry(stack.function()).or_default().calls += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+
+    const FIB: &str = "
+        fn main() {
+            output(fib(input()));
+        }
+
+        fn fib(n) {
+            if n < 2 {
+                return n;
+            }
+            return fib(n - 1) + fib(n - 2);
+        }
+    ";
+
+    #[test]
+    fn test_compiled() {
+        let mut code = Intcode::new(compile(FIB).unwrap());
+        code.add_input(10);
+        let mut stack = CallStack::new();
+        let mut deepest = Vec::new();
+        let output = loop {
+            match stack.step(&mut code) {
+                None => {}
+                Some(Result::Output(o)) => break o,
+                Some(result) => panic!("Expected output, got {:?}", result),
+            }
+            if stack.frames().len() > deepest.len() {
+                deepest = stack.frames().to_vec();
+            }
+        };
+        assert_eq!(output, 55);
+        // main, and fib from 10 down to 1
+        assert_eq!(deepest.len(), 11);
+        let fib = deepest[1].function;
+        assert!(deepest[2..].iter().all(|frame| frame.function == fib));
+        assert_eq!(stack.frames().len(), 1);
+
+        assert_eq!(stack.step(&mut code), None);
+        while stack.step(&mut code).is_none() {}
+        assert!(stack.frames().is_empty());
+    }
+
+    #[test]
+    fn test_profile() {
+        let mut code = Intcode::new(compile(FIB).unwrap());
+        code.add_input(10);
+        let profiles = profile(&mut code);
+        let calls: Vec<u64> = profiles.values().map(|p| p.calls).collect();
+        // Nothing calls the start, main is called once, fib 177 times
+        assert_eq!(calls, vec![0, 1, 177]);
+        let total: u64 = profiles.values().map(|p| p.instructions).sum();
+        assert!(profiles[&0].instructions < 5 && total > 177 * 10);
+    }
+
+    #[test]
+    fn test_day09() {
+        // BOOST in sensor mode calls functions recursively
+        let mut code = Intcode::parse(include_str!("../input/2019/day09.txt"));
+        code.add_input(2);
+        let profiles = profile(&mut code);
+        assert_eq!(profiles.keys().collect::<Vec<_>>(), vec![&0, &922]);
+        assert_eq!(profiles[&922].calls, 37119);
+    }
+}
@@ -0,0 +1,6 @@
+//! Puzzle programs compiled with [`crate::transpile`].
+
+#[rustfmt::skip]
+pub mod day13;
+#[rustfmt::skip]
+pub mod day19;
@@ -0,0 +1,3041 @@
+//! Generated by `cargo run --bin transpile`, don't edit.
+
+#![allow(clippy::all, unreachable_code)]
+
+use crate::{Error, Intcode, Result};
+
+pub const PROG: &[i64] = &[
+    1, 380, 379, 385, 1008, 2823, 432584, 381, 1005, 381, 12, 99, 109, 2824, 1101, 0,
+    0, 383, 1101, 0, 0, 382, 20102, 1, 382, 1, 20102, 1, 383, 2, 21102, 1,
+    37, 0, 1105, 1, 578, 4, 382, 4, 383, 204, 1, 1001, 382, 1, 382, 1007,
+    382, 42, 381, 1005, 381, 22, 1001, 383, 1, 383, 1007, 383, 26, 381, 1005, 381,
+    18, 1006, 385, 69, 99, 104, -1, 104, 0, 4, 386, 3, 384, 1007, 384, 0,
+    381, 1005, 381, 94, 107, 0, 384, 381, 1005, 381, 108, 1105, 1, 161, 107, 1,
+    392, 381, 1006, 381, 161, 1101, -1, 0, 384, 1105, 1, 119, 1007, 392, 40, 381,
+    1006, 381, 161, 1101, 0, 1, 384, 21002, 392, 1, 1, 21102, 24, 1, 2, 21102,
+    0, 1, 3, 21102, 138, 1, 0, 1105, 1, 549, 1, 392, 384, 392, 20102, 1,
+    392, 1, 21101, 0, 24, 2, 21101, 0, 3, 3, 21101, 161, 0, 0, 1105, 1,
+    549, 1101, 0, 0, 384, 20001, 388, 390, 1, 20101, 0, 389, 2, 21101, 0, 180,
+    0, 1106, 0, 578, 1206, 1, 213, 1208, 1, 2, 381, 1006, 381, 205, 20001, 388,
+    390, 1, 21002, 389, 1, 2, 21101, 0, 205, 0, 1106, 0, 393, 1002, 390, -1,
+    390, 1101, 1, 0, 384, 20102, 1, 388, 1, 20001, 389, 391, 2, 21102, 1, 228,
+    0, 1105, 1, 578, 1206, 1, 261, 1208, 1, 2, 381, 1006, 381, 253, 21001, 388,
+    0, 1, 20001, 389, 391, 2, 21101, 253, 0, 0, 1106, 0, 393, 1002, 391, -1,
+    391, 1102, 1, 1, 384, 1005, 384, 161, 20001, 388, 390, 1, 20001, 389, 391, 2,
+    21102, 1, 279, 0, 1105, 1, 578, 1206, 1, 316, 1208, 1, 2, 381, 1006, 381,
+    304, 20001, 388, 390, 1, 20001, 389, 391, 2, 21102, 1, 304, 0, 1106, 0, 393,
+    1002, 390, -1, 390, 1002, 391, -1, 391, 1102, 1, 1, 384, 1005, 384, 161, 21001,
+    388, 0, 1, 21002, 389, 1, 2, 21101, 0, 0, 3, 21101, 0, 338, 0, 1106,
+    0, 549, 1, 388, 390, 388, 1, 389, 391, 389, 21002, 388, 1, 1, 20102, 1,
+    389, 2, 21101, 4, 0, 3, 21102, 1, 365, 0, 1106, 0, 549, 1007, 389, 25,
+    381, 1005, 381, 75, 104, -1, 104, 0, 104, 0, 99, 0, 1, 0, 0, 0,
+    0, 0, 0, 380, 19, 21, 1, 1, 21, 109, 3, 22102, 1, -2, 1, 21201,
+    -1, 0, 2, 21102, 1, 0, 3, 21101, 0, 414, 0, 1105, 1, 549, 21201, -2,
+    0, 1, 21202, -1, 1, 2, 21102, 1, 429, 0, 1105, 1, 601, 1202, 1, 1,
+    435, 1, 386, 0, 386, 104, -1, 104, 0, 4, 386, 1001, 387, -1, 387, 1005,
+    387, 451, 99, 109, -3, 2105, 1, 0, 109, 8, 22202, -7, -6, -3, 22201, -3,
+    -5, -3, 21202, -4, 64, -2, 2207, -3, -2, 381, 1005, 381, 492, 21202, -2, -1,
+    -1, 22201, -3, -1, -3, 2207, -3, -2, 381, 1006, 381, 481, 21202, -4, 8, -2,
+    2207, -3, -2, 381, 1005, 381, 518, 21202, -2, -1, -1, 22201, -3, -1, -3, 2207,
+    -3, -2, 381, 1006, 381, 507, 2207, -3, -4, 381, 1005, 381, 540, 21202, -4, -1,
+    -1, 22201, -3, -1, -3, 2207, -3, -4, 381, 1006, 381, 529, 22102, 1, -3, -7,
+    109, -8, 2105, 1, 0, 109, 4, 1202, -2, 42, 566, 201, -3, 566, 566, 101,
+    639, 566, 566, 2102, 1, -1, 0, 204, -3, 204, -2, 204, -1, 109, -4, 2105,
+    1, 0, 109, 3, 1202, -1, 42, 594, 201, -2, 594, 594, 101, 639, 594, 594,
+    20102, 1, 0, -2, 109, -3, 2105, 1, 0, 109, 3, 22102, 26, -2, 1, 22201,
+    1, -1, 1, 21101, 557, 0, 2, 21102, 671, 1, 3, 21101, 0, 1092, 4, 21101,
+    630, 0, 0, 1105, 1, 456, 21201, 1, 1731, -2, 109, -3, 2106, 0, 0, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 1, 1, 0, 0, 2, 2, 0, 0, 2, 0, 0, 0, 0, 0,
+    2, 2, 2, 0, 2, 2, 2, 0, 0, 2, 0, 0, 0, 0, 0, 2,
+    0, 2, 0, 0, 2, 2, 0, 2, 2, 2, 0, 0, 1, 1, 0, 0,
+    2, 2, 2, 0, 0, 2, 0, 2, 2, 2, 2, 0, 0, 2, 2, 0,
+    2, 2, 2, 2, 0, 0, 0, 0, 2, 0, 2, 0, 2, 2, 2, 2,
+    0, 2, 0, 0, 0, 0, 1, 1, 0, 0, 2, 2, 2, 2, 0, 2,
+    0, 2, 2, 2, 0, 2, 0, 0, 0, 2, 0, 0, 2, 2, 2, 2,
+    2, 0, 2, 0, 0, 0, 2, 2, 0, 0, 2, 2, 2, 2, 2, 0,
+    1, 1, 0, 0, 2, 2, 0, 0, 0, 2, 0, 2, 2, 2, 2, 0,
+    0, 0, 2, 0, 0, 2, 2, 2, 0, 0, 2, 0, 0, 0, 2, 2,
+    2, 0, 2, 0, 0, 0, 2, 0, 2, 0, 1, 1, 0, 2, 0, 0,
+    2, 2, 2, 0, 2, 2, 0, 2, 0, 2, 0, 0, 2, 0, 0, 2,
+    0, 0, 0, 0, 0, 0, 0, 2, 2, 0, 2, 2, 0, 0, 2, 2,
+    2, 2, 2, 0, 1, 1, 0, 2, 0, 2, 2, 2, 2, 0, 2, 0,
+    2, 0, 2, 2, 0, 2, 0, 2, 0, 2, 2, 2, 2, 0, 0, 0,
+    2, 2, 2, 2, 2, 0, 0, 2, 0, 0, 0, 2, 0, 0, 1, 1,
+    0, 2, 0, 2, 2, 2, 2, 0, 2, 2, 0, 2, 2, 2, 2, 2,
+    0, 2, 2, 2, 2, 0, 2, 2, 0, 2, 0, 2, 2, 2, 0, 2,
+    2, 2, 0, 2, 0, 0, 0, 0, 1, 1, 0, 2, 0, 2, 2, 2,
+    2, 2, 2, 2, 2, 0, 0, 2, 0, 0, 2, 2, 2, 2, 2, 0,
+    0, 0, 2, 0, 2, 0, 2, 2, 0, 2, 2, 0, 0, 0, 2, 2,
+    2, 0, 1, 1, 0, 2, 0, 0, 0, 0, 2, 0, 0, 2, 0, 2,
+    0, 2, 0, 0, 2, 2, 2, 2, 2, 0, 2, 0, 0, 2, 0, 2,
+    0, 2, 2, 0, 0, 2, 2, 0, 0, 2, 0, 0, 1, 1, 0, 0,
+    2, 0, 0, 0, 2, 2, 0, 0, 0, 0, 2, 2, 0, 0, 0, 2,
+    2, 0, 2, 0, 0, 2, 2, 0, 2, 2, 0, 0, 2, 0, 2, 2,
+    2, 0, 0, 2, 2, 0, 1, 1, 0, 2, 2, 2, 0, 0, 2, 0,
+    2, 2, 0, 0, 2, 2, 2, 2, 2, 0, 2, 2, 0, 2, 2, 2,
+    2, 2, 0, 2, 0, 0, 2, 0, 2, 0, 2, 0, 2, 2, 2, 0,
+    1, 1, 0, 0, 2, 0, 2, 0, 0, 0, 0, 2, 2, 2, 2, 0,
+    0, 0, 0, 0, 2, 2, 0, 2, 2, 0, 2, 0, 2, 2, 2, 2,
+    0, 0, 0, 0, 2, 2, 0, 2, 2, 0, 1, 1, 0, 0, 0, 2,
+    0, 2, 2, 2, 2, 2, 2, 0, 2, 2, 0, 0, 0, 2, 0, 2,
+    2, 0, 2, 2, 2, 2, 0, 2, 0, 2, 0, 0, 2, 0, 0, 2,
+    2, 2, 2, 0, 1, 1, 0, 0, 0, 2, 2, 0, 2, 2, 0, 2,
+    2, 0, 0, 2, 0, 2, 0, 0, 2, 0, 2, 0, 0, 2, 0, 0,
+    2, 0, 2, 2, 2, 0, 2, 2, 2, 2, 0, 0, 2, 0, 1, 1,
+    0, 0, 0, 2, 2, 0, 2, 2, 2, 0, 2, 2, 0, 0, 2, 0,
+    0, 2, 2, 2, 0, 0, 0, 2, 0, 0, 2, 0, 2, 0, 0, 2,
+    0, 2, 2, 2, 0, 0, 0, 0, 1, 1, 0, 0, 2, 0, 2, 0,
+    2, 0, 0, 2, 2, 2, 0, 2, 2, 2, 2, 0, 2, 2, 0, 2,
+    2, 0, 0, 0, 0, 2, 0, 0, 2, 2, 2, 0, 0, 0, 2, 2,
+    2, 0, 1, 1, 0, 2, 2, 0, 2, 2, 0, 0, 0, 0, 0, 2,
+    2, 2, 0, 2, 2, 2, 0, 2, 2, 0, 0, 2, 2, 0, 2, 0,
+    2, 0, 2, 0, 2, 0, 0, 0, 2, 2, 2, 0, 1, 1, 0, 2,
+    2, 0, 2, 2, 2, 2, 0, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+    2, 0, 2, 0, 2, 2, 0, 0, 2, 2, 0, 2, 0, 2, 2, 2,
+    2, 0, 0, 2, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 1, 26, 39, 93, 24, 77, 14, 80, 83, 52, 91, 93, 34, 84,
+    34, 71, 69, 88, 90, 9, 41, 47, 38, 55, 55, 97, 48, 94, 51, 98,
+    36, 7, 89, 44, 29, 22, 82, 9, 40, 55, 30, 48, 6, 30, 71, 85,
+    16, 56, 22, 20, 34, 83, 79, 18, 54, 97, 37, 1, 18, 41, 60, 62,
+    10, 90, 5, 66, 35, 59, 62, 46, 39, 95, 95, 75, 30, 47, 81, 92,
+    86, 41, 23, 4, 60, 17, 68, 1, 38, 93, 38, 63, 57, 19, 91, 76,
+    36, 17, 39, 33, 15, 10, 38, 55, 89, 90, 80, 33, 41, 68, 68, 88,
+    42, 31, 24, 50, 74, 41, 58, 69, 57, 13, 97, 83, 92, 25, 23, 27,
+    13, 50, 92, 41, 82, 23, 31, 11, 53, 10, 10, 49, 53, 50, 73, 58,
+    12, 84, 58, 10, 95, 1, 28, 76, 2, 53, 86, 66, 98, 20, 88, 30,
+    39, 21, 47, 31, 30, 78, 28, 74, 63, 90, 91, 88, 78, 10, 64, 52,
+    91, 18, 65, 20, 16, 90, 76, 2, 22, 18, 43, 90, 5, 59, 62, 60,
+    71, 41, 66, 64, 39, 32, 90, 10, 61, 52, 71, 46, 61, 18, 53, 82,
+    80, 53, 39, 70, 71, 81, 48, 17, 26, 14, 19, 69, 16, 68, 39, 39,
+    86, 31, 34, 4, 11, 81, 32, 55, 54, 12, 93, 34, 49, 50, 2, 64,
+    61, 44, 89, 13, 45, 64, 45, 87, 92, 16, 81, 83, 18, 4, 96, 43,
+    88, 74, 27, 16, 78, 10, 12, 31, 38, 28, 84, 50, 28, 75, 74, 39,
+    42, 43, 41, 16, 5, 76, 95, 88, 49, 22, 43, 13, 95, 66, 68, 32,
+    79, 66, 26, 82, 43, 25, 79, 10, 2, 77, 22, 19, 81, 4, 92, 42,
+    54, 67, 62, 18, 9, 69, 69, 24, 69, 87, 6, 53, 56, 22, 20, 98,
+    85, 3, 79, 35, 4, 32, 96, 25, 69, 59, 23, 14, 92, 19, 34, 73,
+    42, 44, 45, 7, 85, 8, 31, 55, 68, 84, 59, 4, 18, 65, 19, 61,
+    37, 72, 29, 46, 67, 64, 40, 46, 82, 8, 50, 57, 8, 59, 83, 47,
+    22, 16, 1, 58, 41, 93, 56, 60, 17, 95, 24, 63, 48, 47, 89, 23,
+    12, 37, 82, 19, 9, 74, 30, 15, 65, 1, 86, 88, 93, 75, 73, 11,
+    80, 88, 36, 49, 62, 57, 59, 75, 43, 91, 47, 93, 98, 65, 30, 90,
+    50, 93, 81, 58, 43, 84, 58, 24, 33, 17, 83, 48, 45, 91, 60, 40,
+    87, 75, 94, 26, 95, 81, 93, 62, 68, 73, 71, 77, 56, 89, 49, 26,
+    56, 26, 4, 26, 63, 16, 64, 77, 33, 16, 93, 21, 68, 63, 55, 15,
+    65, 23, 14, 45, 35, 22, 3, 78, 50, 83, 45, 96, 69, 79, 7, 20,
+    6, 75, 64, 8, 7, 71, 85, 16, 68, 27, 50, 88, 31, 34, 50, 32,
+    83, 48, 1, 35, 50, 90, 10, 59, 19, 80, 61, 83, 34, 59, 25, 7,
+    41, 48, 56, 10, 64, 23, 14, 85, 70, 77, 73, 91, 17, 36, 60, 2,
+    1, 77, 89, 39, 84, 89, 76, 55, 77, 51, 24, 43, 2, 31, 50, 46,
+    50, 62, 22, 48, 86, 38, 19, 13, 2, 96, 80, 74, 82, 54, 84, 49,
+    7, 4, 1, 86, 33, 50, 7, 37, 51, 4, 54, 37, 91, 11, 12, 29,
+    77, 78, 55, 98, 15, 34, 82, 7, 28, 41, 16, 42, 73, 44, 62, 70,
+    61, 20, 76, 24, 95, 78, 40, 44, 24, 25, 29, 85, 83, 39, 48, 5,
+    64, 43, 81, 12, 8, 84, 37, 47, 80, 62, 53, 32, 29, 87, 7, 8,
+    41, 1, 72, 9, 38, 58, 64, 31, 6, 23, 15, 31, 3, 53, 56, 34,
+    35, 30, 57, 75, 13, 56, 36, 7, 87, 88, 22, 91, 60, 64, 10, 45,
+    91, 43, 83, 50, 23, 79, 87, 4, 80, 51, 11, 3, 60, 56, 86, 19,
+    83, 76, 75, 43, 44, 35, 75, 25, 97, 40, 20, 11, 51, 31, 1, 23,
+    89, 35, 41, 68, 33, 93, 9, 74, 80, 81, 58, 13, 13, 22, 80, 54,
+    21, 93, 19, 40, 25, 46, 85, 75, 62, 15, 54, 90, 12, 10, 31, 49,
+    89, 51, 13, 57, 92, 3, 17, 28, 84, 36, 90, 21, 12, 86, 44, 87,
+    66, 58, 24, 83, 55, 13, 91, 21, 70, 91, 50, 94, 77, 90, 35, 6,
+    72, 79, 95, 20, 2, 45, 50, 77, 15, 3, 97, 73, 94, 54, 12, 64,
+    30, 9, 26, 45, 84, 34, 95, 57, 15, 88, 33, 47, 62, 69, 62, 89,
+    29, 60, 34, 47, 13, 86, 66, 86, 75, 5, 29, 50, 19, 65, 70, 81,
+    51, 62, 43, 21, 61, 87, 39, 19, 49, 48, 7, 23, 62, 52, 2, 17,
+    31, 54, 97, 98, 83, 76, 98, 4, 30, 31, 56, 11, 89, 7, 75, 46,
+    13, 62, 32, 70, 83, 29, 23, 53, 40, 90, 28, 45, 64, 9, 35, 52,
+    39, 77, 71, 24, 40, 69, 66, 59, 98, 53, 15, 57, 83, 50, 65, 75,
+    26, 1, 83, 17, 73, 75, 34, 72, 11, 66, 18, 50, 77, 8, 26, 80,
+    61, 33, 84, 12, 52, 13, 7, 30, 26, 61, 23, 10, 88, 3, 80, 80,
+    49, 29, 40, 90, 65, 25, 89, 55, 42, 13, 98, 23, 3, 19, 46, 5,
+    94, 5, 19, 72, 63, 2, 20, 36, 26, 20, 4, 51, 77, 93, 2, 25,
+    86, 12, 7, 56, 12, 61, 85, 53, 12, 15, 10, 13, 13, 50, 73, 34,
+    86, 59, 94, 40, 36, 9, 95, 74, 55, 13, 19, 71, 60, 63, 74, 26,
+    24, 10, 5, 21, 86, 93, 62, 62, 34, 47, 85, 26, 94, 60, 25, 9,
+    93, 57, 57, 97, 80, 6, 80, 48, 22, 11, 77, 50, 9, 20, 23, 21,
+    15, 33, 49, 8, 76, 94, 2, 61, 88, 10, 24, 56, 47, 43, 48, 39,
+    12, 52, 66, 19, 68, 35, 26, 46, 93, 27, 51, 72, 98, 58, 1, 24,
+    5, 5, 9, 51, 61, 3, 42, 76, 98, 83, 90, 49, 94, 74, 79, 73,
+    4, 46, 55, 62, 16, 19, 35, 51, 19, 39, 64, 87, 2, 95, 65, 66,
+    26, 81, 67, 35, 54, 51, 70, 10, 63, 76, 51, 82, 67, 48, 78, 15,
+    19, 27, 37, 24, 63, 97, 55, 97, 7, 49, 72, 7, 76, 25, 82, 84,
+    56, 94, 47, 48, 44, 91, 10, 432584,
+];
+
+#[derive(Clone)]
+pub struct Machine {
+    pub code: Intcode,
+}
+
+impl Machine {
+    pub fn new() -> Self {
+        Self::with_code(Intcode::new(PROG.to_vec()))
+    }
+
+    /// Runs the compiled code on the state of `code`, which should be running the same program.
+    pub fn with_code(code: Intcode) -> Self {
+        Machine { code }
+    }
+
+    pub fn add_input(&mut self, input: i64) -> &mut Self {
+        self.code.add_input(input);
+        self
+    }
+
+    /// Runs one instruction in the interpreter.
+    fn interpret(&mut self, ip: &mut i64, rb: &mut i64) -> Option<Result> {
+        self.code.set_ip(*ip);
+        self.code.set_relative_base(*rb);
+        let result = self.code.step();
+        *ip = self.code.ip();
+        *rb = self.code.relative_base();
+        result
+    }
+
+    pub fn run(&mut self) -> Result {
+        let mut ip = self.code.ip();
+        let mut rb = self.code.relative_base();
+        let result = loop {
+            match ip {
+                0 => {
+                    if self.code.prog[0..=3] != [1, 380, 379, 385] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 380);
+                    let b = get(&mut self.code.prog, 379);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 0 }),
+                    };
+                    set(&mut self.code.prog, 385, result);
+                    ip = 4;
+                }
+                4 => {
+                    if self.code.prog[4..=7] != [1008, 2823, 432584, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 2823);
+                    let b = 432584;
+                    set(&mut self.code.prog, 381, (a == b) as i64);
+                    ip = 8;
+                }
+                8 => {
+                    if self.code.prog[8..=10] != [1005, 381, 12] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 12;
+                    } else {
+                        ip = 11;
+                    }
+                }
+                11 => {
+                    if self.code.prog[11..=11] != [99] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    break Result::Halt;
+                }
+                12 => {
+                    if self.code.prog[12..=13] != [109, 2824] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 2824;
+                    ip = 14;
+                }
+                14 => {
+                    if self.code.prog[14..=17] != [1101, 0, 0, 383] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 14 }),
+                    };
+                    set(&mut self.code.prog, 383, result);
+                    ip = 18;
+                }
+                18 => {
+                    if self.code.prog[18..=21] != [1101, 0, 0, 382] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 18 }),
+                    };
+                    set(&mut self.code.prog, 382, result);
+                    ip = 22;
+                }
+                22 => {
+                    if self.code.prog[22..=25] != [20102, 1, 382, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 382);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 22 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 26;
+                }
+                25 => {
+                    if self.code.prog[25..=28] != [1, 20102, 1, 383] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 20102);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 25 }),
+                    };
+                    set(&mut self.code.prog, 383, result);
+                    ip = 29;
+                }
+                26 => {
+                    if self.code.prog[26..=29] != [20102, 1, 383, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 383);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 26 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 30;
+                }
+                29 => {
+                    if self.code.prog[29..=32] != [2, 21102, 1, 37] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 21102);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 29 }),
+                    };
+                    set(&mut self.code.prog, 37, result);
+                    ip = 33;
+                }
+                30 => {
+                    if self.code.prog[30..=33] != [21102, 1, 37, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 37;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 30 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 34;
+                }
+                34 => {
+                    if self.code.prog[34..=36] != [1105, 1, 578] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 578;
+                    } else {
+                        ip = 37;
+                    }
+                }
+                37 => {
+                    if self.code.prog[37..=38] != [4, 382] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 382);
+                    ip = 39;
+                    break Result::Output(a);
+                }
+                39 => {
+                    if self.code.prog[39..=40] != [4, 383] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 383);
+                    ip = 41;
+                    break Result::Output(a);
+                }
+                41 => {
+                    if self.code.prog[41..=42] != [204, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    ip = 43;
+                    break Result::Output(a);
+                }
+                42 => {
+                    if self.code.prog[42..=45] != [1, 1001, 382, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1001);
+                    let b = get(&mut self.code.prog, 382);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 42 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 46;
+                }
+                43 => {
+                    if self.code.prog[43..=46] != [1001, 382, 1, 382] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 382);
+                    let b = 1;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 43 }),
+                    };
+                    set(&mut self.code.prog, 382, result);
+                    ip = 47;
+                }
+                47 => {
+                    if self.code.prog[47..=50] != [1007, 382, 42, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 382);
+                    let b = 42;
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 51;
+                }
+                51 => {
+                    if self.code.prog[51..=53] != [1005, 381, 22] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 22;
+                    } else {
+                        ip = 54;
+                    }
+                }
+                54 => {
+                    if self.code.prog[54..=57] != [1001, 383, 1, 383] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 383);
+                    let b = 1;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 54 }),
+                    };
+                    set(&mut self.code.prog, 383, result);
+                    ip = 58;
+                }
+                58 => {
+                    if self.code.prog[58..=61] != [1007, 383, 26, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 383);
+                    let b = 26;
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 62;
+                }
+                62 => {
+                    if self.code.prog[62..=64] != [1005, 381, 18] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 18;
+                    } else {
+                        ip = 65;
+                    }
+                }
+                65 => {
+                    if self.code.prog[65..=67] != [1006, 385, 69] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 385) == 0 {
+                        ip = 69;
+                    } else {
+                        ip = 68;
+                    }
+                }
+                68 => {
+                    if self.code.prog[68..=68] != [99] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    break Result::Halt;
+                }
+                69 => {
+                    if self.code.prog[69..=70] != [104, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = -1;
+                    ip = 71;
+                    break Result::Output(a);
+                }
+                71 => {
+                    if self.code.prog[71..=72] != [104, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    ip = 73;
+                    break Result::Output(a);
+                }
+                73 => {
+                    if self.code.prog[73..=74] != [4, 386] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 386);
+                    ip = 75;
+                    break Result::Output(a);
+                }
+                75 => {
+                    if self.code.prog[75..=76] != [3, 384] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    match self.code.next_input() {
+                        Some(input) => { set(&mut self.code.prog, 384, input); }
+                        None => break Result::NeedInput,
+                    }
+                    ip = 77;
+                }
+                77 => {
+                    if self.code.prog[77..=80] != [1007, 384, 0, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 384);
+                    let b = 0;
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 81;
+                }
+                81 => {
+                    if self.code.prog[81..=83] != [1005, 381, 94] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 94;
+                    } else {
+                        ip = 84;
+                    }
+                }
+                84 => {
+                    if self.code.prog[84..=87] != [107, 0, 384, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, 384);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 88;
+                }
+                88 => {
+                    if self.code.prog[88..=90] != [1005, 381, 108] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 108;
+                    } else {
+                        ip = 91;
+                    }
+                }
+                91 => {
+                    if self.code.prog[91..=93] != [1105, 1, 161] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 161;
+                    } else {
+                        ip = 94;
+                    }
+                }
+                94 => {
+                    if self.code.prog[94..=97] != [107, 1, 392, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 392);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 98;
+                }
+                98 => {
+                    if self.code.prog[98..=100] != [1006, 381, 161] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 161;
+                    } else {
+                        ip = 101;
+                    }
+                }
+                101 => {
+                    if self.code.prog[101..=104] != [1101, -1, 0, 384] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = -1;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 101 }),
+                    };
+                    set(&mut self.code.prog, 384, result);
+                    ip = 105;
+                }
+                105 => {
+                    if self.code.prog[105..=107] != [1105, 1, 119] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 119;
+                    } else {
+                        ip = 108;
+                    }
+                }
+                108 => {
+                    if self.code.prog[108..=111] != [1007, 392, 40, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 392);
+                    let b = 40;
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 112;
+                }
+                112 => {
+                    if self.code.prog[112..=114] != [1006, 381, 161] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 161;
+                    } else {
+                        ip = 115;
+                    }
+                }
+                115 => {
+                    if self.code.prog[115..=118] != [1101, 0, 1, 384] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 1;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 115 }),
+                    };
+                    set(&mut self.code.prog, 384, result);
+                    ip = 119;
+                }
+                119 => {
+                    if self.code.prog[119..=122] != [21002, 392, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 392);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 119 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 123;
+                }
+                123 => {
+                    if self.code.prog[123..=126] != [21102, 24, 1, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 24;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 123 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 127;
+                }
+                127 => {
+                    if self.code.prog[127..=130] != [21102, 0, 1, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 127 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 131;
+                }
+                131 => {
+                    if self.code.prog[131..=134] != [21102, 138, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 138;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 131 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 135;
+                }
+                135 => {
+                    if self.code.prog[135..=137] != [1105, 1, 549] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 549;
+                    } else {
+                        ip = 138;
+                    }
+                }
+                138 => {
+                    if self.code.prog[138..=141] != [1, 392, 384, 392] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 392);
+                    let b = get(&mut self.code.prog, 384);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 138 }),
+                    };
+                    set(&mut self.code.prog, 392, result);
+                    ip = 142;
+                }
+                142 => {
+                    if self.code.prog[142..=145] != [20102, 1, 392, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 392);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 142 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 146;
+                }
+                146 => {
+                    if self.code.prog[146..=149] != [21101, 0, 24, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 24;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 146 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 150;
+                }
+                150 => {
+                    if self.code.prog[150..=153] != [21101, 0, 3, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 3;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 150 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 154;
+                }
+                154 => {
+                    if self.code.prog[154..=157] != [21101, 161, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 161;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 154 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 158;
+                }
+                158 => {
+                    if self.code.prog[158..=160] != [1105, 1, 549] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 549;
+                    } else {
+                        ip = 161;
+                    }
+                }
+                161 => {
+                    if self.code.prog[161..=164] != [1101, 0, 0, 384] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 161 }),
+                    };
+                    set(&mut self.code.prog, 384, result);
+                    ip = 165;
+                }
+                165 => {
+                    if self.code.prog[165..=168] != [20001, 388, 390, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = get(&mut self.code.prog, 390);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 165 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 169;
+                }
+                169 => {
+                    if self.code.prog[169..=172] != [20101, 0, 389, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, 389);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 169 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 173;
+                }
+                173 => {
+                    if self.code.prog[173..=176] != [21101, 0, 180, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 180;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 173 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 177;
+                }
+                177 => {
+                    if self.code.prog[177..=179] != [1106, 0, 578] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 578;
+                    } else {
+                        ip = 180;
+                    }
+                }
+                180 => {
+                    if self.code.prog[180..=182] != [1206, 1, 213] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, rb + 1) == 0 {
+                        ip = 213;
+                    } else {
+                        ip = 183;
+                    }
+                }
+                183 => {
+                    if self.code.prog[183..=186] != [1208, 1, 2, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 2;
+                    set(&mut self.code.prog, 381, (a == b) as i64);
+                    ip = 187;
+                }
+                187 => {
+                    if self.code.prog[187..=189] != [1006, 381, 205] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 205;
+                    } else {
+                        ip = 190;
+                    }
+                }
+                190 => {
+                    if self.code.prog[190..=193] != [20001, 388, 390, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = get(&mut self.code.prog, 390);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 190 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 194;
+                }
+                194 => {
+                    if self.code.prog[194..=197] != [21002, 389, 1, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 194 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 198;
+                }
+                198 => {
+                    if self.code.prog[198..=201] != [21101, 0, 205, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 205;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 198 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 202;
+                }
+                202 => {
+                    if self.code.prog[202..=204] != [1106, 0, 393] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 393;
+                    } else {
+                        ip = 205;
+                    }
+                }
+                205 => {
+                    if self.code.prog[205..=208] != [1002, 390, -1, 390] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 390);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 205 }),
+                    };
+                    set(&mut self.code.prog, 390, result);
+                    ip = 209;
+                }
+                209 => {
+                    if self.code.prog[209..=212] != [1101, 1, 0, 384] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 209 }),
+                    };
+                    set(&mut self.code.prog, 384, result);
+                    ip = 213;
+                }
+                213 => {
+                    if self.code.prog[213..=216] != [20102, 1, 388, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 388);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 213 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 217;
+                }
+                217 => {
+                    if self.code.prog[217..=220] != [20001, 389, 391, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = get(&mut self.code.prog, 391);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 217 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 221;
+                }
+                221 => {
+                    if self.code.prog[221..=224] != [21102, 1, 228, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 228;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 221 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 225;
+                }
+                225 => {
+                    if self.code.prog[225..=227] != [1105, 1, 578] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 578;
+                    } else {
+                        ip = 228;
+                    }
+                }
+                228 => {
+                    if self.code.prog[228..=230] != [1206, 1, 261] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, rb + 1) == 0 {
+                        ip = 261;
+                    } else {
+                        ip = 231;
+                    }
+                }
+                231 => {
+                    if self.code.prog[231..=234] != [1208, 1, 2, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 2;
+                    set(&mut self.code.prog, 381, (a == b) as i64);
+                    ip = 235;
+                }
+                235 => {
+                    if self.code.prog[235..=237] != [1006, 381, 253] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 253;
+                    } else {
+                        ip = 238;
+                    }
+                }
+                238 => {
+                    if self.code.prog[238..=241] != [21001, 388, 0, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 238 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 242;
+                }
+                242 => {
+                    if self.code.prog[242..=245] != [20001, 389, 391, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = get(&mut self.code.prog, 391);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 242 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 246;
+                }
+                246 => {
+                    if self.code.prog[246..=249] != [21101, 253, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 253;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 246 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 250;
+                }
+                250 => {
+                    if self.code.prog[250..=252] != [1106, 0, 393] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 393;
+                    } else {
+                        ip = 253;
+                    }
+                }
+                253 => {
+                    if self.code.prog[253..=256] != [1002, 391, -1, 391] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 391);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 253 }),
+                    };
+                    set(&mut self.code.prog, 391, result);
+                    ip = 257;
+                }
+                257 => {
+                    if self.code.prog[257..=260] != [1102, 1, 1, 384] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 257 }),
+                    };
+                    set(&mut self.code.prog, 384, result);
+                    ip = 261;
+                }
+                261 => {
+                    if self.code.prog[261..=263] != [1005, 384, 161] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 384) != 0 {
+                        ip = 161;
+                    } else {
+                        ip = 264;
+                    }
+                }
+                264 => {
+                    if self.code.prog[264..=267] != [20001, 388, 390, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = get(&mut self.code.prog, 390);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 264 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 268;
+                }
+                268 => {
+                    if self.code.prog[268..=271] != [20001, 389, 391, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = get(&mut self.code.prog, 391);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 268 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 272;
+                }
+                272 => {
+                    if self.code.prog[272..=275] != [21102, 1, 279, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 279;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 272 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 276;
+                }
+                276 => {
+                    if self.code.prog[276..=278] != [1105, 1, 578] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 578;
+                    } else {
+                        ip = 279;
+                    }
+                }
+                279 => {
+                    if self.code.prog[279..=281] != [1206, 1, 316] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, rb + 1) == 0 {
+                        ip = 316;
+                    } else {
+                        ip = 282;
+                    }
+                }
+                282 => {
+                    if self.code.prog[282..=285] != [1208, 1, 2, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 2;
+                    set(&mut self.code.prog, 381, (a == b) as i64);
+                    ip = 286;
+                }
+                286 => {
+                    if self.code.prog[286..=288] != [1006, 381, 304] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 304;
+                    } else {
+                        ip = 289;
+                    }
+                }
+                289 => {
+                    if self.code.prog[289..=292] != [20001, 388, 390, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = get(&mut self.code.prog, 390);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 289 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 293;
+                }
+                293 => {
+                    if self.code.prog[293..=296] != [20001, 389, 391, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = get(&mut self.code.prog, 391);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 293 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 297;
+                }
+                297 => {
+                    if self.code.prog[297..=300] != [21102, 1, 304, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 304;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 297 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 301;
+                }
+                301 => {
+                    if self.code.prog[301..=303] != [1106, 0, 393] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 393;
+                    } else {
+                        ip = 304;
+                    }
+                }
+                304 => {
+                    if self.code.prog[304..=307] != [1002, 390, -1, 390] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 390);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 304 }),
+                    };
+                    set(&mut self.code.prog, 390, result);
+                    ip = 308;
+                }
+                308 => {
+                    if self.code.prog[308..=311] != [1002, 391, -1, 391] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 391);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 308 }),
+                    };
+                    set(&mut self.code.prog, 391, result);
+                    ip = 312;
+                }
+                312 => {
+                    if self.code.prog[312..=315] != [1102, 1, 1, 384] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 312 }),
+                    };
+                    set(&mut self.code.prog, 384, result);
+                    ip = 316;
+                }
+                316 => {
+                    if self.code.prog[316..=318] != [1005, 384, 161] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 384) != 0 {
+                        ip = 161;
+                    } else {
+                        ip = 319;
+                    }
+                }
+                319 => {
+                    if self.code.prog[319..=322] != [21001, 388, 0, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 319 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 323;
+                }
+                323 => {
+                    if self.code.prog[323..=326] != [21002, 389, 1, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 323 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 327;
+                }
+                327 => {
+                    if self.code.prog[327..=330] != [21101, 0, 0, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 327 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 331;
+                }
+                331 => {
+                    if self.code.prog[331..=334] != [21101, 0, 338, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 338;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 331 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 335;
+                }
+                335 => {
+                    if self.code.prog[335..=337] != [1106, 0, 549] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 549;
+                    } else {
+                        ip = 338;
+                    }
+                }
+                338 => {
+                    if self.code.prog[338..=341] != [1, 388, 390, 388] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = get(&mut self.code.prog, 390);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 338 }),
+                    };
+                    set(&mut self.code.prog, 388, result);
+                    ip = 342;
+                }
+                342 => {
+                    if self.code.prog[342..=345] != [1, 389, 391, 389] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = get(&mut self.code.prog, 391);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 342 }),
+                    };
+                    set(&mut self.code.prog, 389, result);
+                    ip = 346;
+                }
+                346 => {
+                    if self.code.prog[346..=349] != [21002, 388, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 388);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 346 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 350;
+                }
+                350 => {
+                    if self.code.prog[350..=353] != [20102, 1, 389, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 389);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 350 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 354;
+                }
+                354 => {
+                    if self.code.prog[354..=357] != [21101, 4, 0, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 4;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 354 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 358;
+                }
+                358 => {
+                    if self.code.prog[358..=361] != [21102, 1, 365, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 365;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 358 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 362;
+                }
+                362 => {
+                    if self.code.prog[362..=364] != [1106, 0, 549] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 549;
+                    } else {
+                        ip = 365;
+                    }
+                }
+                365 => {
+                    if self.code.prog[365..=368] != [1007, 389, 25, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 389);
+                    let b = 25;
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 369;
+                }
+                369 => {
+                    if self.code.prog[369..=371] != [1005, 381, 75] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 75;
+                    } else {
+                        ip = 372;
+                    }
+                }
+                372 => {
+                    if self.code.prog[372..=373] != [104, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = -1;
+                    ip = 374;
+                    break Result::Output(a);
+                }
+                374 => {
+                    if self.code.prog[374..=375] != [104, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    ip = 376;
+                    break Result::Output(a);
+                }
+                376 => {
+                    if self.code.prog[376..=377] != [104, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    ip = 378;
+                    break Result::Output(a);
+                }
+                378 => {
+                    if self.code.prog[378..=378] != [99] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    break Result::Halt;
+                }
+                393 => {
+                    if self.code.prog[393..=394] != [109, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 3;
+                    ip = 395;
+                }
+                395 => {
+                    if self.code.prog[395..=398] != [22102, 1, -2, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 395 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 399;
+                }
+                399 => {
+                    if self.code.prog[399..=402] != [21201, -1, 0, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 399 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 403;
+                }
+                403 => {
+                    if self.code.prog[403..=406] != [21102, 1, 0, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 0;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 403 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 407;
+                }
+                407 => {
+                    if self.code.prog[407..=410] != [21101, 0, 414, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 414;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 407 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 411;
+                }
+                411 => {
+                    if self.code.prog[411..=413] != [1105, 1, 549] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 549;
+                    } else {
+                        ip = 414;
+                    }
+                }
+                414 => {
+                    if self.code.prog[414..=417] != [21201, -2, 0, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 414 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 418;
+                }
+                418 => {
+                    if self.code.prog[418..=421] != [21202, -1, 1, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 418 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 422;
+                }
+                422 => {
+                    if self.code.prog[422..=425] != [21102, 1, 429, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 429;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 422 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 426;
+                }
+                426 => {
+                    if self.code.prog[426..=428] != [1105, 1, 601] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 601;
+                    } else {
+                        ip = 429;
+                    }
+                }
+                429 => {
+                    if self.code.prog[429..=432] != [1202, 1, 1, 435] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 429 }),
+                    };
+                    set(&mut self.code.prog, 435, result);
+                    ip = 433;
+                }
+                433 => {
+                    if self.code.prog[433..=436] != [1, 386, 0, 386] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 386);
+                    let b = get(&mut self.code.prog, 0);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 433 }),
+                    };
+                    set(&mut self.code.prog, 386, result);
+                    ip = 437;
+                }
+                437 => {
+                    if self.code.prog[437..=438] != [104, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = -1;
+                    ip = 439;
+                    break Result::Output(a);
+                }
+                439 => {
+                    if self.code.prog[439..=440] != [104, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    ip = 441;
+                    break Result::Output(a);
+                }
+                441 => {
+                    if self.code.prog[441..=442] != [4, 386] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 386);
+                    ip = 443;
+                    break Result::Output(a);
+                }
+                443 => {
+                    if self.code.prog[443..=446] != [1001, 387, -1, 387] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 387);
+                    let b = -1;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 443 }),
+                    };
+                    set(&mut self.code.prog, 387, result);
+                    ip = 447;
+                }
+                447 => {
+                    if self.code.prog[447..=449] != [1005, 387, 451] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 387) != 0 {
+                        ip = 451;
+                    } else {
+                        ip = 450;
+                    }
+                }
+                450 => {
+                    if self.code.prog[450..=450] != [99] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    break Result::Halt;
+                }
+                451 => {
+                    if self.code.prog[451..=452] != [109, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -3;
+                    ip = 453;
+                }
+                453 => {
+                    if self.code.prog[453..=455] != [2105, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 456;
+                    }
+                }
+                456 => {
+                    if self.code.prog[456..=457] != [109, 8] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 8;
+                    ip = 458;
+                }
+                458 => {
+                    if self.code.prog[458..=461] != [22202, -7, -6, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -7);
+                    let b = get(&mut self.code.prog, rb + -6);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 458 }),
+                    };
+                    set(&mut self.code.prog, rb + -3, result);
+                    ip = 462;
+                }
+                462 => {
+                    if self.code.prog[462..=465] != [22201, -3, -5, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -5);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 462 }),
+                    };
+                    set(&mut self.code.prog, rb + -3, result);
+                    ip = 466;
+                }
+                466 => {
+                    if self.code.prog[466..=469] != [21202, -4, 64, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = 64;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 466 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 470;
+                }
+                470 => {
+                    if self.code.prog[470..=473] != [2207, -3, -2, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 474;
+                }
+                474 => {
+                    if self.code.prog[474..=476] != [1005, 381, 492] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 492;
+                    } else {
+                        ip = 477;
+                    }
+                }
+                477 => {
+                    if self.code.prog[477..=480] != [21202, -2, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 477 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 481;
+                }
+                481 => {
+                    if self.code.prog[481..=484] != [22201, -3, -1, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 481 }),
+                    };
+                    set(&mut self.code.prog, rb + -3, result);
+                    ip = 485;
+                }
+                485 => {
+                    if self.code.prog[485..=488] != [2207, -3, -2, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 489;
+                }
+                489 => {
+                    if self.code.prog[489..=491] != [1006, 381, 481] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 481;
+                    } else {
+                        ip = 492;
+                    }
+                }
+                492 => {
+                    if self.code.prog[492..=495] != [21202, -4, 8, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = 8;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 492 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 496;
+                }
+                496 => {
+                    if self.code.prog[496..=499] != [2207, -3, -2, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 500;
+                }
+                500 => {
+                    if self.code.prog[500..=502] != [1005, 381, 518] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 518;
+                    } else {
+                        ip = 503;
+                    }
+                }
+                503 => {
+                    if self.code.prog[503..=506] != [21202, -2, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 503 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 507;
+                }
+                507 => {
+                    if self.code.prog[507..=510] != [22201, -3, -1, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 507 }),
+                    };
+                    set(&mut self.code.prog, rb + -3, result);
+                    ip = 511;
+                }
+                511 => {
+                    if self.code.prog[511..=514] != [2207, -3, -2, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 515;
+                }
+                515 => {
+                    if self.code.prog[515..=517] != [1006, 381, 507] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 507;
+                    } else {
+                        ip = 518;
+                    }
+                }
+                518 => {
+                    if self.code.prog[518..=521] != [2207, -3, -4, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -4);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 522;
+                }
+                522 => {
+                    if self.code.prog[522..=524] != [1005, 381, 540] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) != 0 {
+                        ip = 540;
+                    } else {
+                        ip = 525;
+                    }
+                }
+                525 => {
+                    if self.code.prog[525..=528] != [21202, -4, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 525 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 529;
+                }
+                529 => {
+                    if self.code.prog[529..=532] != [22201, -3, -1, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 529 }),
+                    };
+                    set(&mut self.code.prog, rb + -3, result);
+                    ip = 533;
+                }
+                533 => {
+                    if self.code.prog[533..=536] != [2207, -3, -4, 381] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -4);
+                    set(&mut self.code.prog, 381, (a < b) as i64);
+                    ip = 537;
+                }
+                537 => {
+                    if self.code.prog[537..=539] != [1006, 381, 529] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, 381) == 0 {
+                        ip = 529;
+                    } else {
+                        ip = 540;
+                    }
+                }
+                540 => {
+                    if self.code.prog[540..=543] != [22102, 1, -3, -7] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, rb + -3);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 540 }),
+                    };
+                    set(&mut self.code.prog, rb + -7, result);
+                    ip = 544;
+                }
+                544 => {
+                    if self.code.prog[544..=545] != [109, -8] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -8;
+                    ip = 546;
+                }
+                546 => {
+                    if self.code.prog[546..=548] != [2105, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 549;
+                    }
+                }
+                549 => {
+                    if self.code.prog[549..=550] != [109, 4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 4;
+                    ip = 551;
+                }
+                551 => {
+                    if self.code.prog[551..=554] != [1202, -2, 42, 566] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = 42;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 551 }),
+                    };
+                    set(&mut self.code.prog, 566, result);
+                    ip = 555;
+                }
+                555 => {
+                    if self.code.prog[555..=558] != [201, -3, 566, 566] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, 566);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 555 }),
+                    };
+                    set(&mut self.code.prog, 566, result);
+                    ip = 559;
+                }
+                559 => {
+                    if self.code.prog[559..=562] != [101, 639, 566, 566] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 639;
+                    let b = get(&mut self.code.prog, 566);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 559 }),
+                    };
+                    set(&mut self.code.prog, 566, result);
+                    ip = 563;
+                }
+                563 => {
+                    if self.code.prog[563..=566] != [2102, 1, -1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 563 }),
+                    };
+                    set(&mut self.code.prog, 0, result);
+                    ip = 567;
+                }
+                567 => {
+                    if self.code.prog[567..=568] != [204, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    ip = 569;
+                    break Result::Output(a);
+                }
+                569 => {
+                    if self.code.prog[569..=570] != [204, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    ip = 571;
+                    break Result::Output(a);
+                }
+                571 => {
+                    if self.code.prog[571..=572] != [204, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    ip = 573;
+                    break Result::Output(a);
+                }
+                573 => {
+                    if self.code.prog[573..=574] != [109, -4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -4;
+                    ip = 575;
+                }
+                575 => {
+                    if self.code.prog[575..=577] != [2105, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 578;
+                    }
+                }
+                578 => {
+                    if self.code.prog[578..=579] != [109, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 3;
+                    ip = 580;
+                }
+                580 => {
+                    if self.code.prog[580..=583] != [1202, -1, 42, 594] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    let b = 42;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 580 }),
+                    };
+                    set(&mut self.code.prog, 594, result);
+                    ip = 584;
+                }
+                584 => {
+                    if self.code.prog[584..=587] != [201, -2, 594, 594] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = get(&mut self.code.prog, 594);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 584 }),
+                    };
+                    set(&mut self.code.prog, 594, result);
+                    ip = 588;
+                }
+                588 => {
+                    if self.code.prog[588..=591] != [101, 639, 594, 594] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 639;
+                    let b = get(&mut self.code.prog, 594);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 588 }),
+                    };
+                    set(&mut self.code.prog, 594, result);
+                    ip = 592;
+                }
+                592 => {
+                    if self.code.prog[592..=595] != [20102, 1, 0, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 0);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 592 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 596;
+                }
+                596 => {
+                    if self.code.prog[596..=597] != [109, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -3;
+                    ip = 598;
+                }
+                598 => {
+                    if self.code.prog[598..=600] != [2105, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 601;
+                    }
+                }
+                601 => {
+                    if self.code.prog[601..=602] != [109, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 3;
+                    ip = 603;
+                }
+                603 => {
+                    if self.code.prog[603..=606] != [22102, 26, -2, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 26;
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 603 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 607;
+                }
+                607 => {
+                    if self.code.prog[607..=610] != [22201, 1, -1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 607 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 611;
+                }
+                611 => {
+                    if self.code.prog[611..=614] != [21101, 557, 0, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 557;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 611 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 615;
+                }
+                615 => {
+                    if self.code.prog[615..=618] != [21102, 671, 1, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 671;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 615 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 619;
+                }
+                619 => {
+                    if self.code.prog[619..=622] != [21101, 0, 1092, 4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 1092;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 619 }),
+                    };
+                    set(&mut self.code.prog, rb + 4, result);
+                    ip = 623;
+                }
+                623 => {
+                    if self.code.prog[623..=626] != [21101, 630, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 630;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 623 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 627;
+                }
+                627 => {
+                    if self.code.prog[627..=629] != [1105, 1, 456] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 456;
+                    } else {
+                        ip = 630;
+                    }
+                }
+                630 => {
+                    if self.code.prog[630..=633] != [21201, 1, 1731, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 1731;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 630 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 634;
+                }
+                634 => {
+                    if self.code.prog[634..=635] != [109, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -3;
+                    ip = 636;
+                }
+                636 => {
+                    if self.code.prog[636..=638] != [2106, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 639;
+                    }
+                }
+                639 => {
+                    if self.code.prog[639..=642] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 639 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 643;
+                }
+                643 => {
+                    if self.code.prog[643..=646] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 643 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 647;
+                }
+                647 => {
+                    if self.code.prog[647..=650] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 647 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 651;
+                }
+                651 => {
+                    if self.code.prog[651..=654] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 651 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 655;
+                }
+                655 => {
+                    if self.code.prog[655..=658] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 655 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 659;
+                }
+                659 => {
+                    if self.code.prog[659..=662] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 659 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 663;
+                }
+                663 => {
+                    if self.code.prog[663..=666] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 663 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 667;
+                }
+                667 => {
+                    if self.code.prog[667..=670] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 667 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 671;
+                }
+                671 => {
+                    if self.code.prog[671..=674] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 671 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 675;
+                }
+                675 => {
+                    if self.code.prog[675..=678] != [1, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 675 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 679;
+                }
+                679 => {
+                    if self.code.prog[679..=682] != [1, 1, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 1);
+                    let b = get(&mut self.code.prog, 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 679 }),
+                    };
+                    set(&mut self.code.prog, 0, result);
+                    ip = 683;
+                }
+                _ => {
+                    if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                        break result;
+                    }
+                }
+            }
+        };
+        self.code.set_ip(ip);
+        self.code.set_relative_base(rb);
+        result
+    }
+}
+
+fn get(prog: &mut Vec<i64>, p: i64) -> i64 {
+    if let Some(&value) = prog.get(p as usize) {
+        return value;
+    }
+    prog.resize(p as usize + 1, 0);
+    0
+}
+
+fn set(prog: &mut Vec<i64>, p: i64, value: i64) {
+    if p as usize >= prog.len() {
+        prog.resize(p as usize + 1, 0);
+    }
+    prog[p as usize] = value;
+}
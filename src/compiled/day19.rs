@@ -0,0 +1,1842 @@
+//! Generated by `cargo run --bin transpile`, don't edit.
+
+#![allow(clippy::all, unreachable_code)]
+
+use crate::{Error, Intcode, Result};
+
+pub const PROG: &[i64] = &[
+    109, 424, 203, 1, 21102, 11, 1, 0, 1106, 0, 282, 21101, 0, 18, 0, 1106,
+    0, 259, 1201, 1, 0, 221, 203, 1, 21102, 1, 31, 0, 1106, 0, 282, 21101,
+    0, 38, 0, 1106, 0, 259, 20102, 1, 23, 2, 21202, 1, 1, 3, 21101, 1,
+    0, 1, 21101, 0, 57, 0, 1105, 1, 303, 2101, 0, 1, 222, 20101, 0, 221,
+    3, 21001, 221, 0, 2, 21102, 1, 259, 1, 21101, 0, 80, 0, 1105, 1, 225,
+    21101, 185, 0, 2, 21102, 91, 1, 0, 1106, 0, 303, 1202, 1, 1, 223, 21001,
+    222, 0, 4, 21102, 259, 1, 3, 21101, 225, 0, 2, 21102, 1, 225, 1, 21101,
+    0, 118, 0, 1106, 0, 225, 20102, 1, 222, 3, 21102, 1, 131, 2, 21101, 133,
+    0, 0, 1106, 0, 303, 21202, 1, -1, 1, 22001, 223, 1, 1, 21101, 148, 0,
+    0, 1105, 1, 259, 2101, 0, 1, 223, 21002, 221, 1, 4, 21002, 222, 1, 3,
+    21101, 0, 16, 2, 1001, 132, -2, 224, 1002, 224, 2, 224, 1001, 224, 3, 224,
+    1002, 132, -1, 132, 1, 224, 132, 224, 21001, 224, 1, 1, 21101, 0, 195, 0,
+    106, 0, 109, 20207, 1, 223, 2, 20101, 0, 23, 1, 21102, 1, -1, 3, 21101,
+    0, 214, 0, 1105, 1, 303, 22101, 1, 1, 1, 204, 1, 99, 0, 0, 0,
+    0, 109, 5, 1201, -4, 0, 249, 22101, 0, -3, 1, 22101, 0, -2, 2, 21201,
+    -1, 0, 3, 21101, 0, 250, 0, 1106, 0, 225, 21201, 1, 0, -4, 109, -5,
+    2106, 0, 0, 109, 3, 22107, 0, -2, -1, 21202, -1, 2, -1, 21201, -1, -1,
+    -1, 22202, -1, -2, -2, 109, -3, 2106, 0, 0, 109, 3, 21207, -2, 0, -1,
+    1206, -1, 294, 104, 0, 99, 22102, 1, -2, -2, 109, -3, 2105, 1, 0, 109,
+    5, 22207, -3, -4, -1, 1206, -1, 346, 22201, -4, -3, -4, 21202, -3, -1, -1,
+    22201, -4, -1, 2, 21202, 2, -1, -1, 22201, -4, -1, 1, 21201, -2, 0, 3,
+    21101, 343, 0, 0, 1106, 0, 303, 1105, 1, 415, 22207, -2, -3, -1, 1206, -1,
+    387, 22201, -3, -2, -3, 21202, -2, -1, -1, 22201, -3, -1, 3, 21202, 3, -1,
+    -1, 22201, -3, -1, 2, 22101, 0, -4, 1, 21102, 384, 1, 0, 1106, 0, 303,
+    1105, 1, 415, 21202, -4, -1, -4, 22201, -4, -3, -4, 22202, -3, -2, -2, 22202,
+    -2, -4, -4, 22202, -3, -2, -3, 21202, -4, -1, -2, 22201, -3, -2, 1, 21201,
+    1, 0, -4, 109, -5, 2106, 0, 0,
+];
+
+#[derive(Clone)]
+pub struct Machine {
+    pub code: Intcode,
+}
+
+impl Machine {
+    pub fn new() -> Self {
+        Self::with_code(Intcode::new(PROG.to_vec()))
+    }
+
+    /// Runs the compiled code on the state of `code`, which should be running the same program.
+    pub fn with_code(code: Intcode) -> Self {
+        Machine { code }
+    }
+
+    pub fn add_input(&mut self, input: i64) -> &mut Self {
+        self.code.add_input(input);
+        self
+    }
+
+    /// Runs one instruction in the interpreter.
+    fn interpret(&mut self, ip: &mut i64, rb: &mut i64) -> Option<Result> {
+        self.code.set_ip(*ip);
+        self.code.set_relative_base(*rb);
+        let result = self.code.step();
+        *ip = self.code.ip();
+        *rb = self.code.relative_base();
+        result
+    }
+
+    pub fn run(&mut self) -> Result {
+        let mut ip = self.code.ip();
+        let mut rb = self.code.relative_base();
+        let result = loop {
+            match ip {
+                0 => {
+                    if self.code.prog[0..=1] != [109, 424] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 424;
+                    ip = 2;
+                }
+                2 => {
+                    if self.code.prog[2..=3] != [203, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    match self.code.next_input() {
+                        Some(input) => { set(&mut self.code.prog, rb + 1, input); }
+                        None => break Result::NeedInput,
+                    }
+                    ip = 4;
+                }
+                3 => {
+                    if self.code.prog[3..=6] != [1, 21102, 11, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 21102);
+                    let b = get(&mut self.code.prog, 11);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 3 }),
+                    };
+                    set(&mut self.code.prog, 1, result);
+                    ip = 7;
+                }
+                4 => {
+                    if self.code.prog[4..=7] != [21102, 11, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 11;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 4 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 8;
+                }
+                8 => {
+                    if self.code.prog[8..=10] != [1106, 0, 282] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 282;
+                    } else {
+                        ip = 11;
+                    }
+                }
+                11 => {
+                    if self.code.prog[11..=14] != [21101, 0, 18, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 18;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 11 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 15;
+                }
+                15 => {
+                    if self.code.prog[15..=17] != [1106, 0, 259] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 259;
+                    } else {
+                        ip = 18;
+                    }
+                }
+                18 => {
+                    if self.code.prog[18..=21] != [1201, 1, 0, 221] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 18 }),
+                    };
+                    set(&mut self.code.prog, 221, result);
+                    ip = 22;
+                }
+                22 => {
+                    if self.code.prog[22..=23] != [203, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    match self.code.next_input() {
+                        Some(input) => { set(&mut self.code.prog, rb + 1, input); }
+                        None => break Result::NeedInput,
+                    }
+                    ip = 24;
+                }
+                24 => {
+                    if self.code.prog[24..=27] != [21102, 1, 31, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 31;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 24 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 28;
+                }
+                28 => {
+                    if self.code.prog[28..=30] != [1106, 0, 282] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 282;
+                    } else {
+                        ip = 31;
+                    }
+                }
+                31 => {
+                    if self.code.prog[31..=34] != [21101, 0, 38, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 38;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 31 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 35;
+                }
+                35 => {
+                    if self.code.prog[35..=37] != [1106, 0, 259] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 259;
+                    } else {
+                        ip = 38;
+                    }
+                }
+                38 => {
+                    if self.code.prog[38..=41] != [20102, 1, 23, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 23);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 38 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 42;
+                }
+                42 => {
+                    if self.code.prog[42..=45] != [21202, 1, 1, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 42 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 46;
+                }
+                46 => {
+                    if self.code.prog[46..=49] != [21101, 1, 0, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 46 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 50;
+                }
+                50 => {
+                    if self.code.prog[50..=53] != [21101, 0, 57, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 57;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 50 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 54;
+                }
+                54 => {
+                    if self.code.prog[54..=56] != [1105, 1, 303] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 303;
+                    } else {
+                        ip = 57;
+                    }
+                }
+                57 => {
+                    if self.code.prog[57..=60] != [2101, 0, 1, 222] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, rb + 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 57 }),
+                    };
+                    set(&mut self.code.prog, 222, result);
+                    ip = 61;
+                }
+                61 => {
+                    if self.code.prog[61..=64] != [20101, 0, 221, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, 221);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 61 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 65;
+                }
+                65 => {
+                    if self.code.prog[65..=68] != [21001, 221, 0, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 221);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 65 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 69;
+                }
+                69 => {
+                    if self.code.prog[69..=72] != [21102, 1, 259, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 259;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 69 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 73;
+                }
+                73 => {
+                    if self.code.prog[73..=76] != [21101, 0, 80, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 80;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 73 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 77;
+                }
+                77 => {
+                    if self.code.prog[77..=79] != [1105, 1, 225] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 225;
+                    } else {
+                        ip = 80;
+                    }
+                }
+                80 => {
+                    if self.code.prog[80..=83] != [21101, 185, 0, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 185;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 80 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 84;
+                }
+                84 => {
+                    if self.code.prog[84..=87] != [21102, 91, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 91;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 84 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 88;
+                }
+                88 => {
+                    if self.code.prog[88..=90] != [1106, 0, 303] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 303;
+                    } else {
+                        ip = 91;
+                    }
+                }
+                91 => {
+                    if self.code.prog[91..=94] != [1202, 1, 1, 223] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 91 }),
+                    };
+                    set(&mut self.code.prog, 223, result);
+                    ip = 95;
+                }
+                95 => {
+                    if self.code.prog[95..=98] != [21001, 222, 0, 4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 222);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 95 }),
+                    };
+                    set(&mut self.code.prog, rb + 4, result);
+                    ip = 99;
+                }
+                99 => {
+                    if self.code.prog[99..=102] != [21102, 259, 1, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 259;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 99 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 103;
+                }
+                103 => {
+                    if self.code.prog[103..=106] != [21101, 225, 0, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 225;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 103 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 107;
+                }
+                107 => {
+                    if self.code.prog[107..=110] != [21102, 1, 225, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 225;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 107 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 111;
+                }
+                111 => {
+                    if self.code.prog[111..=114] != [21101, 0, 118, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 118;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 111 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 115;
+                }
+                115 => {
+                    if self.code.prog[115..=117] != [1106, 0, 225] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 225;
+                    } else {
+                        ip = 118;
+                    }
+                }
+                118 => {
+                    if self.code.prog[118..=121] != [20102, 1, 222, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, 222);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 118 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 122;
+                }
+                122 => {
+                    if self.code.prog[122..=125] != [21102, 1, 131, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = 131;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 122 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 126;
+                }
+                126 => {
+                    if self.code.prog[126..=129] != [21101, 133, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 133;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 126 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 130;
+                }
+                130 => {
+                    if self.code.prog[130..=132] != [1106, 0, 303] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 303;
+                    } else {
+                        ip = 133;
+                    }
+                }
+                133 => {
+                    if self.code.prog[133..=136] != [21202, 1, -1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 133 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 137;
+                }
+                137 => {
+                    if self.code.prog[137..=140] != [22001, 223, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 223);
+                    let b = get(&mut self.code.prog, rb + 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 137 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 141;
+                }
+                141 => {
+                    if self.code.prog[141..=144] != [21101, 148, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 148;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 141 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 145;
+                }
+                145 => {
+                    if self.code.prog[145..=147] != [1105, 1, 259] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 259;
+                    } else {
+                        ip = 148;
+                    }
+                }
+                148 => {
+                    if self.code.prog[148..=151] != [2101, 0, 1, 223] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, rb + 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 148 }),
+                    };
+                    set(&mut self.code.prog, 223, result);
+                    ip = 152;
+                }
+                152 => {
+                    if self.code.prog[152..=155] != [21002, 221, 1, 4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 221);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 152 }),
+                    };
+                    set(&mut self.code.prog, rb + 4, result);
+                    ip = 156;
+                }
+                156 => {
+                    if self.code.prog[156..=159] != [21002, 222, 1, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 222);
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 156 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 160;
+                }
+                160 => {
+                    if self.code.prog[160..=163] != [21101, 0, 16, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 16;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 160 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 164;
+                }
+                164 => {
+                    if self.code.prog[164..=167] != [1001, 132, -2, 224] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 132);
+                    let b = -2;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 164 }),
+                    };
+                    set(&mut self.code.prog, 224, result);
+                    ip = 168;
+                }
+                168 => {
+                    if self.code.prog[168..=171] != [1002, 224, 2, 224] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 224);
+                    let b = 2;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 168 }),
+                    };
+                    set(&mut self.code.prog, 224, result);
+                    ip = 172;
+                }
+                172 => {
+                    if self.code.prog[172..=175] != [1001, 224, 3, 224] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 224);
+                    let b = 3;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 172 }),
+                    };
+                    set(&mut self.code.prog, 224, result);
+                    ip = 176;
+                }
+                176 => {
+                    if self.code.prog[176..=179] != [1002, 132, -1, 132] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 132);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 176 }),
+                    };
+                    set(&mut self.code.prog, 132, result);
+                    ip = 180;
+                }
+                180 => {
+                    if self.code.prog[180..=183] != [1, 224, 132, 224] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 224);
+                    let b = get(&mut self.code.prog, 132);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 180 }),
+                    };
+                    set(&mut self.code.prog, 224, result);
+                    ip = 184;
+                }
+                184 => {
+                    if self.code.prog[184..=187] != [21001, 224, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, 224);
+                    let b = 1;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 184 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 188;
+                }
+                188 => {
+                    if self.code.prog[188..=191] != [21101, 0, 195, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 195;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 188 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 192;
+                }
+                192 => {
+                    if self.code.prog[192..=194] != [106, 0, 109] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = get(&mut self.code.prog, 109);
+                    } else {
+                        ip = 195;
+                    }
+                }
+                195 => {
+                    if self.code.prog[195..=198] != [20207, 1, 223, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = get(&mut self.code.prog, 223);
+                    set(&mut self.code.prog, rb + 2, (a < b) as i64);
+                    ip = 199;
+                }
+                199 => {
+                    if self.code.prog[199..=202] != [20101, 0, 23, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, 23);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 199 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 203;
+                }
+                203 => {
+                    if self.code.prog[203..=206] != [21102, 1, -1, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 203 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 207;
+                }
+                207 => {
+                    if self.code.prog[207..=210] != [21101, 0, 214, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 214;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 207 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 211;
+                }
+                211 => {
+                    if self.code.prog[211..=213] != [1105, 1, 303] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 303;
+                    } else {
+                        ip = 214;
+                    }
+                }
+                214 => {
+                    if self.code.prog[214..=217] != [22101, 1, 1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, rb + 1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 214 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 218;
+                }
+                218 => {
+                    if self.code.prog[218..=219] != [204, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    ip = 220;
+                    break Result::Output(a);
+                }
+                220 => {
+                    if self.code.prog[220..=220] != [99] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    break Result::Halt;
+                }
+                225 => {
+                    if self.code.prog[225..=226] != [109, 5] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 5;
+                    ip = 227;
+                }
+                227 => {
+                    if self.code.prog[227..=230] != [1201, -4, 0, 249] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 227 }),
+                    };
+                    set(&mut self.code.prog, 249, result);
+                    ip = 231;
+                }
+                231 => {
+                    if self.code.prog[231..=234] != [22101, 0, -3, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, rb + -3);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 231 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 235;
+                }
+                235 => {
+                    if self.code.prog[235..=238] != [22101, 0, -2, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 235 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 239;
+                }
+                239 => {
+                    if self.code.prog[239..=242] != [21201, -1, 0, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 239 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 243;
+                }
+                243 => {
+                    if self.code.prog[243..=246] != [21101, 0, 250, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = 250;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 243 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 247;
+                }
+                247 => {
+                    if self.code.prog[247..=249] != [1106, 0, 225] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 225;
+                    } else {
+                        ip = 250;
+                    }
+                }
+                250 => {
+                    if self.code.prog[250..=253] != [21201, 1, 0, -4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 250 }),
+                    };
+                    set(&mut self.code.prog, rb + -4, result);
+                    ip = 254;
+                }
+                254 => {
+                    if self.code.prog[254..=255] != [109, -5] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -5;
+                    ip = 256;
+                }
+                256 => {
+                    if self.code.prog[256..=258] != [2106, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 259;
+                    }
+                }
+                259 => {
+                    if self.code.prog[259..=260] != [109, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 3;
+                    ip = 261;
+                }
+                261 => {
+                    if self.code.prog[261..=264] != [22107, 0, -2, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, rb + -2);
+                    set(&mut self.code.prog, rb + -1, (a < b) as i64);
+                    ip = 265;
+                }
+                265 => {
+                    if self.code.prog[265..=268] != [21202, -1, 2, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    let b = 2;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 265 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 269;
+                }
+                269 => {
+                    if self.code.prog[269..=272] != [21201, -1, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    let b = -1;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 269 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 273;
+                }
+                273 => {
+                    if self.code.prog[273..=276] != [22202, -1, -2, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -1);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 273 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 277;
+                }
+                277 => {
+                    if self.code.prog[277..=278] != [109, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -3;
+                    ip = 279;
+                }
+                279 => {
+                    if self.code.prog[279..=281] != [2106, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 282;
+                    }
+                }
+                282 => {
+                    if self.code.prog[282..=283] != [109, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 3;
+                    ip = 284;
+                }
+                284 => {
+                    if self.code.prog[284..=287] != [21207, -2, 0, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = 0;
+                    set(&mut self.code.prog, rb + -1, (a < b) as i64);
+                    ip = 288;
+                }
+                288 => {
+                    if self.code.prog[288..=290] != [1206, -1, 294] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, rb + -1) == 0 {
+                        ip = 294;
+                    } else {
+                        ip = 291;
+                    }
+                }
+                291 => {
+                    if self.code.prog[291..=292] != [104, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    ip = 293;
+                    break Result::Output(a);
+                }
+                293 => {
+                    if self.code.prog[293..=293] != [99] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    break Result::Halt;
+                }
+                294 => {
+                    if self.code.prog[294..=297] != [22102, 1, -2, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 1;
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 294 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 298;
+                }
+                298 => {
+                    if self.code.prog[298..=299] != [109, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -3;
+                    ip = 300;
+                }
+                300 => {
+                    if self.code.prog[300..=302] != [2105, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 303;
+                    }
+                }
+                303 => {
+                    if self.code.prog[303..=304] != [109, 5] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += 5;
+                    ip = 305;
+                }
+                305 => {
+                    if self.code.prog[305..=308] != [22207, -3, -4, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -4);
+                    set(&mut self.code.prog, rb + -1, (a < b) as i64);
+                    ip = 309;
+                }
+                309 => {
+                    if self.code.prog[309..=311] != [1206, -1, 346] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, rb + -1) == 0 {
+                        ip = 346;
+                    } else {
+                        ip = 312;
+                    }
+                }
+                312 => {
+                    if self.code.prog[312..=315] != [22201, -4, -3, -4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = get(&mut self.code.prog, rb + -3);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 312 }),
+                    };
+                    set(&mut self.code.prog, rb + -4, result);
+                    ip = 316;
+                }
+                316 => {
+                    if self.code.prog[316..=319] != [21202, -3, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 316 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 320;
+                }
+                320 => {
+                    if self.code.prog[320..=323] != [22201, -4, -1, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 320 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 324;
+                }
+                324 => {
+                    if self.code.prog[324..=327] != [21202, 2, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 2);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 324 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 328;
+                }
+                328 => {
+                    if self.code.prog[328..=331] != [22201, -4, -1, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 328 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 332;
+                }
+                332 => {
+                    if self.code.prog[332..=335] != [21201, -2, 0, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 332 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 336;
+                }
+                336 => {
+                    if self.code.prog[336..=339] != [21101, 343, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 343;
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 336 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 340;
+                }
+                340 => {
+                    if self.code.prog[340..=342] != [1106, 0, 303] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 303;
+                    } else {
+                        ip = 343;
+                    }
+                }
+                343 => {
+                    if self.code.prog[343..=345] != [1105, 1, 415] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 415;
+                    } else {
+                        ip = 346;
+                    }
+                }
+                346 => {
+                    if self.code.prog[346..=349] != [22207, -2, -3, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = get(&mut self.code.prog, rb + -3);
+                    set(&mut self.code.prog, rb + -1, (a < b) as i64);
+                    ip = 350;
+                }
+                350 => {
+                    if self.code.prog[350..=352] != [1206, -1, 387] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if get(&mut self.code.prog, rb + -1) == 0 {
+                        ip = 387;
+                    } else {
+                        ip = 353;
+                    }
+                }
+                353 => {
+                    if self.code.prog[353..=356] != [22201, -3, -2, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 353 }),
+                    };
+                    set(&mut self.code.prog, rb + -3, result);
+                    ip = 357;
+                }
+                357 => {
+                    if self.code.prog[357..=360] != [21202, -2, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 357 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 361;
+                }
+                361 => {
+                    if self.code.prog[361..=364] != [22201, -3, -1, 3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 361 }),
+                    };
+                    set(&mut self.code.prog, rb + 3, result);
+                    ip = 365;
+                }
+                365 => {
+                    if self.code.prog[365..=368] != [21202, 3, -1, -1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 3);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 365 }),
+                    };
+                    set(&mut self.code.prog, rb + -1, result);
+                    ip = 369;
+                }
+                369 => {
+                    if self.code.prog[369..=372] != [22201, -3, -1, 2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -1);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 369 }),
+                    };
+                    set(&mut self.code.prog, rb + 2, result);
+                    ip = 373;
+                }
+                373 => {
+                    if self.code.prog[373..=376] != [22101, 0, -4, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 0;
+                    let b = get(&mut self.code.prog, rb + -4);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 373 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 377;
+                }
+                377 => {
+                    if self.code.prog[377..=380] != [21102, 384, 1, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = 384;
+                    let b = 1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 377 }),
+                    };
+                    set(&mut self.code.prog, rb + 0, result);
+                    ip = 381;
+                }
+                381 => {
+                    if self.code.prog[381..=383] != [1106, 0, 303] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = 303;
+                    } else {
+                        ip = 384;
+                    }
+                }
+                384 => {
+                    if self.code.prog[384..=386] != [1105, 1, 415] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 1 != 0 {
+                        ip = 415;
+                    } else {
+                        ip = 387;
+                    }
+                }
+                387 => {
+                    if self.code.prog[387..=390] != [21202, -4, -1, -4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 387 }),
+                    };
+                    set(&mut self.code.prog, rb + -4, result);
+                    ip = 391;
+                }
+                391 => {
+                    if self.code.prog[391..=394] != [22201, -4, -3, -4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = get(&mut self.code.prog, rb + -3);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 391 }),
+                    };
+                    set(&mut self.code.prog, rb + -4, result);
+                    ip = 395;
+                }
+                395 => {
+                    if self.code.prog[395..=398] != [22202, -3, -2, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 395 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 399;
+                }
+                399 => {
+                    if self.code.prog[399..=402] != [22202, -2, -4, -4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -2);
+                    let b = get(&mut self.code.prog, rb + -4);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 399 }),
+                    };
+                    set(&mut self.code.prog, rb + -4, result);
+                    ip = 403;
+                }
+                403 => {
+                    if self.code.prog[403..=406] != [22202, -3, -2, -3] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 403 }),
+                    };
+                    set(&mut self.code.prog, rb + -3, result);
+                    ip = 407;
+                }
+                407 => {
+                    if self.code.prog[407..=410] != [21202, -4, -1, -2] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -4);
+                    let b = -1;
+                    let result = match self.code.overflow().mul(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 407 }),
+                    };
+                    set(&mut self.code.prog, rb + -2, result);
+                    ip = 411;
+                }
+                411 => {
+                    if self.code.prog[411..=414] != [22201, -3, -2, 1] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + -3);
+                    let b = get(&mut self.code.prog, rb + -2);
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 411 }),
+                    };
+                    set(&mut self.code.prog, rb + 1, result);
+                    ip = 415;
+                }
+                415 => {
+                    if self.code.prog[415..=418] != [21201, 1, 0, -4] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    let a = get(&mut self.code.prog, rb + 1);
+                    let b = 0;
+                    let result = match self.code.overflow().add(a, b) {
+                        Some(result) => result,
+                        None => break Result::Error(Error::Overflow { ip: 415 }),
+                    };
+                    set(&mut self.code.prog, rb + -4, result);
+                    ip = 419;
+                }
+                419 => {
+                    if self.code.prog[419..=420] != [109, -5] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    rb += -5;
+                    ip = 421;
+                }
+                421 => {
+                    if self.code.prog[421..=423] != [2106, 0, 0] {
+                        if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                            break result;
+                        }
+                        continue;
+                    }
+                    if 0 == 0 {
+                        ip = get(&mut self.code.prog, rb + 0);
+                    } else {
+                        ip = 424;
+                    }
+                }
+                _ => {
+                    if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                        break result;
+                    }
+                }
+            }
+        };
+        self.code.set_ip(ip);
+        self.code.set_relative_base(rb);
+        result
+    }
+}
+
+fn get(prog: &mut Vec<i64>, p: i64) -> i64 {
+    if let Some(&value) = prog.get(p as usize) {
+        return value;
+    }
+    prog.resize(p as usize + 1, 0);
+    0
+}
+
+fn set(prog: &mut Vec<i64>, p: i64, value: i64) {
+    if p as usize >= prog.len() {
+        prog.resize(p as usize + 1, 0);
+    }
+    prog[p as usize] = value;
+}
@@ -0,0 +1,926 @@
+//! A compiler from a tiny imperative language to Intcode, for writing test programs:
+//!
+//! ```text
+//! fn main() {
+//!     let n = input();
+//!     let i = 0;
+//!     while i < n {
+//!         output(fib(i));
+//!         i = i + 1;
+//!     }
+//! }
+//!
+//! fn fib(n) {
+//!     if n < 2 {
+//!         return n;
+//!     }
+//!     return fib(n - 1) + fib(n - 2);
+//! }
+//! ```
+//!
+//! All values are integers, `0` is false and anything else true. Expressions are numbers,
+//! variables, `+ - *`, comparisons (`== != < <= > >=`), `!`, unary `-`, short-circuiting `&&` and
+//! `||`, calls and `input()`. Statements are `let`, assignment, `if`/`else`, `while`, `return`,
+//! `output(x);` and calls. `//` starts a comment. The program starts at `main`, which has no
+//! parameters, and halts when it returns.
+//!
+//! Variables live in stack frames addressed with the relative base, the stack starts after the
+//! code. A frame has the return address, the return value, the parameters and then the locals and
+//! temporaries. A call writes the return address and arguments into the frame above the caller's,
+//! moves the relative base there and jumps. Returning stores the return value and jumps to the
+//! return address, then the caller moves the relative base back.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+/// Compiles `source` to a program for [`crate::Intcode::new`].
+pub fn compile(source: &str) -> Result<Vec<i64>, Error> {
+    let tokens = lex(source)?;
+    let functions = Parser { tokens, pos: 0 }.program()?;
+    Codegen::default().program(&functions)
+}
+
+fn error<T>(line: usize, message: impl Into<String>) -> Result<T, Error> {
+    Err(Error {
+        line,
+        message: message.into(),
+    })
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Number(i64),
+    Ident(String),
+    Symbol(&'static str),
+    End,
+}
+
+const SYMBOLS: &[&str] = &[
+    "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "<", ">", "!", "=", "(", ")", "{", "}", ",",
+    ";",
+];
+
+fn lex(source: &str) -> Result<Vec<(Token, usize)>, Error> {
+    let mut tokens = Vec::new();
+    for (i, mut line) in source.lines().enumerate() {
+        let number = i + 1;
+        if let Some(comment) = line.find("//") {
+            line = &line[..comment];
+        }
+        let mut rest = line.trim_start();
+        while !rest.is_empty() {
+            let c = rest.chars().next().unwrap();
+            let len = if c.is_ascii_digit() {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                match rest[..len].parse() {
+                    Ok(n) => tokens.push((Token::Number(n), number)),
+                    Err(_) => return error(number, format!("Number {} too large", &rest[..len])),
+                }
+                len
+            } else if c.is_alphabetic() || c == '_' {
+                let len = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                tokens.push((Token::Ident(rest[..len].to_string()), number));
+                len
+            } else if let Some(&symbol) = SYMBOLS.iter().find(|s| rest.starts_with(**s)) {
+                tokens.push((Token::Symbol(symbol), number));
+                symbol.len()
+            } else {
+                return error(number, format!("Unexpected character {:?}", c));
+            };
+            rest = rest[len..].trim_start();
+        }
+    }
+    let last = source.lines().count().max(1);
+    tokens.push((Token::End, last));
+    Ok(tokens)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Number(i64),
+    Var(String, usize),
+    Input,
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>, usize),
+}
+
+#[derive(Debug)]
+enum Stmt {
+    Let(String, Expr),
+    Assign(String, Expr, usize),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    Return(Option<Expr>),
+    Output(Expr),
+    Expr(Expr),
+}
+
+#[derive(Debug)]
+struct Function {
+    name: String,
+    params: Vec<String>,
+    body: Vec<Stmt>,
+    line: usize,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    fn line(&self) -> usize {
+        self.tokens[self.pos].1
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].0.clone();
+        if token != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn accept(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Token::Symbol(s) if *s == symbol) || self.peek_keyword(symbol) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Ident(name) if name == keyword)
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), Error> {
+        if self.accept(symbol) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{}`", symbol))
+        }
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T, Error> {
+        let found = match self.peek() {
+            Token::Number(n) => format!("`{}`", n),
+            Token::Ident(name) => format!("`{}`", name),
+            Token::Symbol(s) => format!("`{}`", s),
+            Token::End => "end of input".to_string(),
+        };
+        error(
+            self.line(),
+            format!("Expected {}, found {}", expected, found),
+        )
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Token::Ident(name) if !KEYWORDS.contains(&name.as_str()) => match self.next() {
+                Token::Ident(name) => Ok(name),
+                _ => unreachable!(),
+            },
+            _ => self.unexpected("a name"),
+        }
+    }
+
+    fn program(&mut self) -> Result<Vec<Function>, Error> {
+        let mut functions = Vec::new();
+        while *self.peek() != Token::End {
+            let line = self.line();
+            self.expect("fn")?;
+            let name = self.ident()?;
+            self.expect("(")?;
+            let mut params = Vec::new();
+            if !self.accept(")") {
+                loop {
+                    params.push(self.ident()?);
+                    if self.accept(")") {
+                        break;
+                    }
+                    self.expect(",")?;
+                }
+            }
+            let body = self.block()?;
+            functions.push(Function {
+                name,
+                params,
+                body,
+                line,
+            });
+        }
+        Ok(functions)
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>, Error> {
+        self.expect("{")?;
+        let mut stmts = Vec::new();
+        while !self.accept("}") {
+            stmts.push(self.stmt()?);
+        }
+        Ok(stmts)
+    }
+
+    fn stmt(&mut self) -> Result<Stmt, Error> {
+        let line = self.line();
+        let stmt = if self.accept("let") {
+            let name = self.ident()?;
+            self.expect("=")?;
+            Stmt::Let(name, self.expr()?)
+        } else if self.accept("if") {
+            return self.if_stmt();
+        } else if self.accept("while") {
+            let cond = self.expr()?;
+            return Ok(Stmt::While(cond, self.block()?));
+        } else if self.accept("return") {
+            if *self.peek() == Token::Symbol(";") {
+                Stmt::Return(None)
+            } else {
+                Stmt::Return(Some(self.expr()?))
+            }
+        } else if self.accept("output") {
+            self.expect("(")?;
+            let value = self.expr()?;
+            self.expect(")")?;
+            Stmt::Output(value)
+        } else if self.tokens.get(self.pos + 1).map(|t| &t.0) == Some(&Token::Symbol("=")) {
+            let name = self.ident()?;
+            self.expect("=")?;
+            Stmt::Assign(name, self.expr()?, line)
+        } else {
+            Stmt::Expr(self.expr()?)
+        };
+        self.expect(";")?;
+        Ok(stmt)
+    }
+
+    fn if_stmt(&mut self) -> Result<Stmt, Error> {
+        let cond = self.expr()?;
+        let then = self.block()?;
+        let otherwise = if !self.accept("else") {
+            Vec::new()
+        } else if self.accept("if") {
+            vec![self.if_stmt()?]
+        } else {
+            self.block()?
+        };
+        Ok(Stmt::If(cond, then, otherwise))
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let mut left = self.and()?;
+        while self.accept("||") {
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, Error> {
+        let mut left = self.comparison()?;
+        while self.accept("&&") {
+            left = Expr::And(Box::new(left), Box::new(self.comparison()?));
+        }
+        Ok(left)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, Error> {
+        let left = self.sum()?;
+        let ops = [
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ];
+        for &(symbol, op) in &ops {
+            if self.accept(symbol) {
+                return Ok(Expr::Binary(op, Box::new(left), Box::new(self.sum()?)));
+            }
+        }
+        Ok(left)
+    }
+
+    fn sum(&mut self) -> Result<Expr, Error> {
+        let mut left = self.product()?;
+        loop {
+            let op = if self.accept("+") {
+                Op::Add
+            } else if self.accept("-") {
+                Op::Sub
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> Result<Expr, Error> {
+        let mut left = self.unary()?;
+        while self.accept("*") {
+            left = Expr::Binary(Op::Mul, Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        if self.accept("-") {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else if self.accept("!") {
+            Ok(Expr::Not(Box::new(self.unary()?)))
+        } else {
+            self.atom()
+        }
+    }
+
+    fn atom(&mut self) -> Result<Expr, Error> {
+        let line = self.line();
+        if self.accept("input") {
+            self.expect("(")?;
+            self.expect(")")?;
+            return Ok(Expr::Input);
+        }
+        match self.peek() {
+            Token::Number(n) => {
+                let n = *n;
+                self.pos += 1;
+                Ok(Expr::Number(n))
+            }
+            Token::Symbol("(") => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            _ => {
+                let name = self.ident()?;
+                if !self.accept("(") {
+                    return Ok(Expr::Var(name, line));
+                }
+                let mut args = Vec::new();
+                if !self.accept(")") {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.accept(")") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Ok(Expr::Call(name, args, line))
+            }
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "fn", "let", "if", "else", "while", "return", "input", "output",
+];
+
+/// Slots of a frame, relative to the relative base.
+const RETURN_ADDRESS: i64 = 0;
+const RETURN_VALUE: i64 = 1;
+const PARAMS: i64 = 2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operand {
+    Imm(i64),
+    Slot(i64),
+}
+
+/// A parameter value that might only be known at the end.
+#[derive(Clone, Copy, Debug)]
+enum Value {
+    Const(i64),
+    Label(usize),
+    /// The size of the current function's frame times the sign, plus the offset.
+    Frame(i64, i64),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Param {
+    mode: i64,
+    value: Value,
+}
+
+fn imm(value: Value) -> Param {
+    Param { mode: 1, value }
+}
+
+fn rel(value: Value) -> Param {
+    Param { mode: 2, value }
+}
+
+impl From<Operand> for Param {
+    fn from(operand: Operand) -> Self {
+        match operand {
+            Operand::Imm(v) => imm(Value::Const(v)),
+            Operand::Slot(s) => rel(Value::Const(s)),
+        }
+    }
+}
+
+fn slot(s: i64) -> Param {
+    rel(Value::Const(s))
+}
+
+fn constant(v: i64) -> Param {
+    imm(Value::Const(v))
+}
+
+#[derive(Default)]
+struct Codegen {
+    code: Vec<i64>,
+    labels: Vec<Option<usize>>,
+    label_fixups: Vec<(usize, usize)>,
+    /// Function name to label and number of parameters
+    functions: BTreeMap<String, (usize, usize)>,
+    // State of the current function
+    frame_fixups: Vec<(usize, i64)>,
+    scopes: Vec<Vec<(String, i64)>>,
+    next: i64,
+    size: i64,
+}
+
+impl Codegen {
+    fn program(mut self, functions: &[Function]) -> Result<Vec<i64>, Error> {
+        for function in functions {
+            let label = self.label();
+            let previous = self
+                .functions
+                .insert(function.name.clone(), (label, function.params.len()));
+            if previous.is_some() {
+                return error(
+                    function.line,
+                    format!("Function {} is defined twice", function.name),
+                );
+            }
+        }
+        let main = match self.functions.get("main") {
+            Some(&(label, 0)) => label,
+            Some(_) => return error(1, "Function main can't have parameters"),
+            None => return error(1, "No function main"),
+        };
+
+        let stack = self.label();
+        let exit = self.label();
+        self.emit(9, &[imm(Value::Label(stack))]);
+        self.emit(
+            1,
+            &[imm(Value::Label(exit)), constant(0), slot(RETURN_ADDRESS)],
+        );
+        self.emit(5, &[constant(1), imm(Value::Label(main))]);
+        self.place(exit);
+        self.emit(99, &[]);
+
+        for function in functions {
+            self.function(function)?;
+        }
+
+        self.place(stack);
+        for &(position, label) in &self.label_fixups {
+            self.code[position] = self.labels[label].unwrap() as i64;
+        }
+        Ok(self.code)
+    }
+
+    fn function(&mut self, function: &Function) -> Result<(), Error> {
+        self.place(self.functions[&function.name].0);
+        let params = function.params.iter().cloned().zip(PARAMS..).collect();
+        self.scopes = vec![params];
+        self.next = PARAMS + function.params.len() as i64;
+        self.size = self.next;
+        for stmt in &function.body {
+            self.stmt(stmt)?;
+        }
+        self.ret(Operand::Imm(0));
+
+        for (position, sign) in self.frame_fixups.drain(..) {
+            self.code[position] += sign * self.size;
+        }
+        Ok(())
+    }
+
+    fn block(&mut self, stmts: &[Stmt]) -> Result<(), Error> {
+        let next = self.next;
+        self.scopes.push(Vec::new());
+        for stmt in stmts {
+            self.stmt(stmt)?;
+        }
+        self.scopes.pop();
+        self.next = next;
+        Ok(())
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> Result<(), Error> {
+        let mark = self.next;
+        match stmt {
+            Stmt::Let(name, value) => {
+                let value = self.expr(value)?;
+                self.next = mark;
+                let slot = self.alloc();
+                self.copy(value, slot);
+                self.scopes.last_mut().unwrap().push((name.clone(), slot));
+                return Ok(());
+            }
+            Stmt::Assign(name, value, line) => {
+                let slot = self.lookup(name, *line)?;
+                let value = self.expr(value)?;
+                self.copy(value, slot);
+            }
+            Stmt::If(cond, then, otherwise) => {
+                let cond = self.expr(cond)?;
+                self.next = mark;
+                let (other, end) = (self.label(), self.label());
+                self.emit(6, &[cond.into(), imm(Value::Label(other))]);
+                self.block(then)?;
+                if !otherwise.is_empty() {
+                    self.emit(5, &[constant(1), imm(Value::Label(end))]);
+                }
+                self.place(other);
+                self.block(otherwise)?;
+                self.place(end);
+            }
+            Stmt::While(cond, body) => {
+                let (start, end) = (self.label(), self.label());
+                self.place(start);
+                let cond = self.expr(cond)?;
+                self.next = mark;
+                self.emit(6, &[cond.into(), imm(Value::Label(end))]);
+                self.block(body)?;
+                self.emit(5, &[constant(1), imm(Value::Label(start))]);
+                self.place(end);
+            }
+            Stmt::Return(value) => {
+                let value = match value {
+                    Some(value) => self.expr(value)?,
+                    None => Operand::Imm(0),
+                };
+                self.ret(value);
+            }
+            Stmt::Output(value) => {
+                let value = self.expr(value)?;
+                self.emit(4, &[value.into()]);
+            }
+            Stmt::Expr(value) => {
+                self.expr(value)?;
+            }
+        }
+        self.next = mark;
+        Ok(())
+    }
+
+    /// Evaluates `expr`, using slots from `self.next` for temporaries. Returns where the value is,
+    /// which is only valid until the slots are reused.
+    fn expr(&mut self, expr: &Expr) -> Result<Operand, Error> {
+        let mark = self.next;
+        let operand = match expr {
+            Expr::Number(n) => Operand::Imm(*n),
+            Expr::Var(name, line) => Operand::Slot(self.lookup(name, *line)?),
+            Expr::Input => {
+                let t = self.alloc();
+                self.emit(3, &[slot(t)]);
+                Operand::Slot(t)
+            }
+            Expr::Neg(value) => match self.expr(value)? {
+                Operand::Imm(v) => Operand::Imm(v.wrapping_neg()),
+                value => {
+                    self.next = mark;
+                    let t = self.alloc();
+                    self.emit(2, &[value.into(), constant(-1), slot(t)]);
+                    Operand::Slot(t)
+                }
+            },
+            Expr::Not(value) => match self.expr(value)? {
+                Operand::Imm(v) => Operand::Imm((v == 0) as i64),
+                value => {
+                    self.next = mark;
+                    let t = self.alloc();
+                    self.emit(8, &[value.into(), constant(0), slot(t)]);
+                    Operand::Slot(t)
+                }
+            },
+            Expr::Binary(op, a, b) => {
+                let a = self.expr(a)?;
+                let b = self.expr(b)?;
+                self.next = mark;
+                if let (Operand::Imm(a), Operand::Imm(b)) = (a, b) {
+                    Operand::Imm(fold(*op, a, b))
+                } else {
+                    let t = self.alloc();
+                    self.binary(*op, a, b, t);
+                    Operand::Slot(t)
+                }
+            }
+            Expr::And(a, b) | Expr::Or(a, b) => {
+                // For `&&`, a false `a` is the result, for `||` a true one
+                let (jump, short) = match expr {
+                    Expr::And(..) => (6, 0),
+                    _ => (5, 1),
+                };
+                let t = self.alloc();
+                let (done, end) = (self.label(), self.label());
+                let a = self.expr(a)?;
+                self.emit(jump, &[a.into(), imm(Value::Label(done))]);
+                self.next = t + 1;
+                let b = self.expr(b)?;
+                self.emit(8, &[b.into(), constant(0), slot(t)]);
+                self.emit(8, &[slot(t), constant(0), slot(t)]);
+                self.emit(5, &[constant(1), imm(Value::Label(end))]);
+                self.place(done);
+                self.emit(1, &[constant(short), constant(0), slot(t)]);
+                self.place(end);
+                self.next = t + 1;
+                Operand::Slot(t)
+            }
+            Expr::Call(name, args, line) => {
+                let (label, arity) = match self.functions.get(name) {
+                    Some(&f) => f,
+                    None => return error(*line, format!("Unknown function {}", name)),
+                };
+                if args.len() != arity {
+                    let message = format!(
+                        "Function {} takes {} arguments but got {}",
+                        name,
+                        arity,
+                        args.len()
+                    );
+                    return error(*line, message);
+                }
+                let mut values = Vec::new();
+                for arg in args {
+                    values.push(self.expr(arg)?);
+                }
+                for (i, value) in values.into_iter().enumerate() {
+                    let param = rel(Value::Frame(1, PARAMS + i as i64));
+                    self.emit(1, &[value.into(), constant(0), param]);
+                }
+                let ret = self.label();
+                let address = rel(Value::Frame(1, RETURN_ADDRESS));
+                self.emit(1, &[imm(Value::Label(ret)), constant(0), address]);
+                self.emit(9, &[imm(Value::Frame(1, 0))]);
+                self.emit(5, &[constant(1), imm(Value::Label(label))]);
+                self.place(ret);
+                self.emit(9, &[imm(Value::Frame(-1, 0))]);
+                self.next = mark;
+                let t = self.alloc();
+                let result = rel(Value::Frame(1, RETURN_VALUE));
+                self.emit(1, &[result, constant(0), slot(t)]);
+                Operand::Slot(t)
+            }
+        };
+        Ok(operand)
+    }
+
+    fn binary(&mut self, op: Op, a: Operand, b: Operand, t: i64) {
+        match op {
+            Op::Add => self.emit(1, &[a.into(), b.into(), slot(t)]),
+            Op::Mul => self.emit(2, &[a.into(), b.into(), slot(t)]),
+            Op::Sub => match b {
+                Operand::Imm(b) => self.emit(1, &[a.into(), constant(b.wrapping_neg()), slot(t)]),
+                // `t` may hold `a`, so negate `b` into the next slot (which may hold `b`)
+                b => {
+                    let negated = self.alloc();
+                    self.emit(2, &[b.into(), constant(-1), slot(negated)]);
+                    self.emit(1, &[a.into(), slot(negated), slot(t)]);
+                    self.next = t + 1;
+                }
+            },
+            Op::Eq => self.emit(8, &[a.into(), b.into(), slot(t)]),
+            Op::Lt => self.emit(7, &[a.into(), b.into(), slot(t)]),
+            Op::Gt => self.emit(7, &[b.into(), a.into(), slot(t)]),
+            Op::Ne | Op::Le | Op::Ge => {
+                match op {
+                    Op::Ne => self.emit(8, &[a.into(), b.into(), slot(t)]),
+                    Op::Le => self.emit(7, &[b.into(), a.into(), slot(t)]),
+                    _ => self.emit(7, &[a.into(), b.into(), slot(t)]),
+                }
+                self.emit(8, &[slot(t), constant(0), slot(t)]);
+            }
+        }
+    }
+
+    fn ret(&mut self, value: Operand) {
+        self.copy(value, RETURN_VALUE);
+        self.emit(6, &[constant(0), slot(RETURN_ADDRESS)]);
+    }
+
+    fn copy(&mut self, value: Operand, to: i64) {
+        if value != Operand::Slot(to) {
+            self.emit(1, &[value.into(), constant(0), slot(to)]);
+        }
+    }
+
+    fn lookup(&self, name: &str, line: usize) -> Result<i64, Error> {
+        let mut variables = self.scopes.iter().flat_map(|scope| scope.iter()).rev();
+        match variables.find(|(n, _)| n == name) {
+            Some(&(_, slot)) => Ok(slot),
+            None => error(line, format!("Unknown variable {}", name)),
+        }
+    }
+
+    fn alloc(&mut self) -> i64 {
+        let slot = self.next;
+        self.next += 1;
+        self.size = self.size.max(self.next);
+        slot
+    }
+
+    fn label(&mut self) -> usize {
+        self.labels.push(None);
+        self.labels.len() - 1
+    }
+
+    fn place(&mut self, label: usize) {
+        self.labels[label] = Some(self.code.len());
+    }
+
+    fn emit(&mut self, opcode: i64, params: &[Param]) {
+        let modes: i64 = params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| param.mode * 10i64.pow(i as u32 + 2))
+            .sum();
+        self.code.push(opcode + modes);
+        for param in params {
+            let value = match param.value {
+                Value::Const(v) => v,
+                Value::Label(label) => {
+                    self.label_fixups.push((self.code.len(), label));
+                    0
+                }
+                Value::Frame(sign, offset) => {
+                    self.frame_fixups.push((self.code.len(), sign));
+                    offset
+                }
+            };
+            self.code.push(value);
+        }
+    }
+}
+
+fn fold(op: Op, a: i64, b: i64) -> i64 {
+    match op {
+        Op::Add => a.wrapping_add(b),
+        Op::Sub => a.wrapping_sub(b),
+        Op::Mul => a.wrapping_mul(b),
+        Op::Eq => (a == b) as i64,
+        Op::Ne => (a != b) as i64,
+        Op::Lt => (a < b) as i64,
+        Op::Le => (a <= b) as i64,
+        Op::Gt => (a > b) as i64,
+        Op::Ge => (a >= b) as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Intcode;
+
+    fn run(source: &str, inputs: &[i64]) -> Vec<i64> {
+        let mut code = Intcode::new(compile(source).unwrap());
+        for &input in inputs {
+            code.add_input(input);
+        }
+        code.run_all()
+    }
+
+    #[test]
+    fn test_fib() {
+        let source = "
+            fn main() {
+                let n = input();
+                let i = 0;
+                while i < n {
+                    output(fib(i));
+                    i = i + 1;
+                }
+            }
+
+            // Recursive, to exercise the stack
+            fn fib(n) {
+                if n < 2 {
+                    return n;
+                }
+                return fib(n - 1) + fib(n - 2);
+            }
+        ";
+        assert_eq!(run(source, &[10]), vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    }
+
+    #[test]
+    fn test_expressions() {
+        let source = "
+            fn main() {
+                let a = input();
+                let b = input();
+                output(a - b);
+                output(-a * (b + 1) - 3);
+                output((a < b) + (a <= b) * 10 + (a > b) * 100 + (a >= b) * 1000);
+                output((a == b) + (a != b) * 10 + !a * 100 + !!b * 1000);
+                if a > 0 && b > 0 { output(1); } else if a > 0 || b > 0 { output(2); } else { output(3); }
+                output(2 * 3 - 10);
+            }
+        ";
+        assert_eq!(run(source, &[7, 3]), vec![4, -31, 1100, 1010, 1, -4]);
+        assert_eq!(run(source, &[0, 3]), vec![-3, -3, 11, 1110, 2, -4]);
+        assert_eq!(run(source, &[-1, -1]), vec![0, -3, 1010, 1001, 3, -4]);
+    }
+
+    #[test]
+    fn test_short_circuit_and_scopes() {
+        let source = "
+            fn main() {
+                let x = 1;
+                if 0 && side(10) { output(0); }
+                if 1 || side(20) { output(x); }
+                if side(30) && 1 {
+                    let x = 2;
+                    output(x);
+                }
+                output(x);
+                output(sum3(1, 2, side(4)));
+                return;
+                output(99);
+            }
+
+            fn side(v) {
+                output(v);
+                return v;
+            }
+
+            fn sum3(a, b, c) {
+                let total = a + b;
+                return total + c;
+            }
+        ";
+        assert_eq!(run(source, &[]), vec![1, 30, 2, 1, 4, 7]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |source: &str| compile(source).unwrap_err().to_string();
+        assert_eq!(err("fn f() {}"), "Line 1: No function main");
+        assert_eq!(
+            err("fn main() {\n  let x = y;\n}"),
+            "Line 2: Unknown variable y"
+        );
+        assert_eq!(
+            err("fn main() {\n  f(1);\n}\nfn f() {}"),
+            "Line 2: Function f takes 0 arguments but got 1"
+        );
+        assert_eq!(
+            err("fn main() {\n  output(1)\n}"),
+            "Line 3: Expected `;`, found `}`"
+        );
+        assert_eq!(
+            err("fn main() { let while = 1; }"),
+            "Line 1: Expected a name, found `while`"
+        );
+        assert_eq!(err("fn main() { # }"), "Line 1: Unexpected character '#'");
+    }
+}
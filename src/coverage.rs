@@ -0,0 +1,419 @@
+//! Coverage-guided fuzzing of the inputs of a program, to find behaviour that the usual inputs
+//! don't reach, e.g. hidden messages, other modes or crashes.
+//!
+//! Each run is on a fresh clone of the program. The [`Fuzzer`] keeps inputs that cover a new
+//! edge, a pair of consecutive instruction addresses, so both outcomes of each branch count. New
+//! inputs are made by mutating ones from the corpus, preferring values that appear in the program
+//! (e.g. what an input is compared with), small numbers and ASCII. Runs that fail are reported
+//! as a [`Crash`], once per kind of fault and address.
+
+use crate::fuzz::quietly;
+use crate::{Error, Intcode, Result};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Fault {
+    /// The machine returned an error, e.g. an overflow with [`crate::Overflow::Trap`]
+    Error(Error),
+    /// The machine panicked, e.g. on an unknown instruction or a negative address
+    Panic { ip: i64, message: String },
+    /// Detected as looping, or still running after the maximum number of steps
+    Hang { ip: i64 },
+}
+
+impl Fault {
+    /// Faults with the same key are reported once.
+    fn key(&self) -> (u8, i64) {
+        match self {
+            Fault::Error(Error::Overflow { ip }) => (0, *ip),
+            Fault::Panic { ip, .. } => (1, *ip),
+            Fault::Hang { ip } => (2, *ip),
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::Error(e) => write!(f, "{}", e),
+            Fault::Panic { ip, message } => write!(f, "Panic at ip {}: {}", ip, message),
+            Fault::Hang { ip } => write!(f, "Hang at ip {}", ip),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Crash {
+    /// The inputs the program read before the fault
+    pub inputs: Vec<i64>,
+    pub fault: Fault,
+}
+
+struct Run {
+    /// Pairs of consecutive addresses, starting with `(-1, 0)`
+    edges: BTreeSet<(i64, i64)>,
+    /// Number of inputs the program read
+    consumed: usize,
+    fault: Option<Fault>,
+}
+
+pub struct Fuzzer {
+    code: Intcode,
+    max_steps: usize,
+    max_inputs: usize,
+    random: u64,
+    dictionary: Vec<i64>,
+    corpus: Vec<Vec<i64>>,
+    edges: BTreeSet<(i64, i64)>,
+    faults: BTreeSet<(u8, i64)>,
+    dir: Option<PathBuf>,
+}
+
+impl Fuzzer {
+    pub fn new(code: Intcode, seed: u64) -> Self {
+        let dictionary: BTreeSet<i64> = code.prog.iter().copied().collect();
+        Fuzzer {
+            code,
+            max_steps: 100_000,
+            max_inputs: 64,
+            random: seed.max(1),
+            dictionary: dictionary.into_iter().collect(),
+            corpus: Vec::new(),
+            edges: BTreeSet::new(),
+            faults: BTreeSet::new(),
+            dir: None,
+        }
+    }
+
+    /// Steps after which a run counts as a hang, 100 000 by default.
+    pub fn max_steps(&mut self, max_steps: usize) -> &mut Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Maximum number of inputs per run, 64 by default.
+    pub fn max_inputs(&mut self, max_inputs: usize) -> &mut Self {
+        self.max_inputs = max_inputs;
+        self
+    }
+
+    /// Loads the corpus from `dir`, and stores new entries there. Each file has the inputs of one
+    /// entry, e.g. `1,2,3`. Crashes are stored in `crashes`, with the fault on a second line.
+    pub fn corpus_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<&mut Self> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir.join("crashes"))?;
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut corpus = Vec::new();
+        for path in paths {
+            let text = fs::read_to_string(&path)?;
+            match parse(text.trim()) {
+                Some(inputs) => corpus.push(inputs),
+                None => {
+                    let message = format!("Invalid inputs in {:?}", path);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+                }
+            }
+        }
+        quietly(|| {
+            for inputs in corpus {
+                self.add(inputs);
+            }
+        });
+        self.dir = Some(dir.to_path_buf());
+        Ok(self)
+    }
+
+    /// Runs `inputs` and adds them to the corpus if they cover a new edge. Returns the crash if
+    /// it's a new fault.
+    pub fn add(&mut self, mut inputs: Vec<i64>) -> Option<Crash> {
+        let run = self.execute(&inputs);
+        inputs.truncate(run.consumed);
+        if !run.edges.is_subset(&self.edges) {
+            self.edges.extend(run.edges);
+            self.save(&inputs, None);
+            self.corpus.push(inputs.clone());
+        }
+        let fault = run.fault?;
+        if !self.faults.insert(fault.key()) {
+            return None;
+        }
+        self.save(&inputs, Some(&fault));
+        Some(Crash { inputs, fault })
+    }
+
+    /// Runs `iterations` mutated inputs, returns the new crashes.
+    pub fn fuzz(&mut self, iterations: usize) -> Vec<Crash> {
+        quietly(|| {
+            let mut crashes = Vec::new();
+            if self.corpus.is_empty() {
+                crashes.extend(self.add(Vec::new()));
+            }
+            for _ in 0..iterations {
+                let inputs = self.mutate();
+                crashes.extend(self.add(inputs));
+            }
+            crashes
+        })
+    }
+
+    pub fn corpus(&self) -> &[Vec<i64>] {
+        &self.corpus
+    }
+
+    /// Number of edges covered so far.
+    pub fn edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Number of instruction addresses reached so far.
+    pub fn addresses(&self) -> usize {
+        let addresses: BTreeSet<i64> = self.edges.iter().map(|&(_, to)| to).collect();
+        addresses.len()
+    }
+
+    fn execute(&self, inputs: &[i64]) -> Run {
+        let mut code = self.code.clone();
+        code.detect_loops();
+        for &input in inputs {
+            code.add_input(input);
+        }
+        let mut edges = BTreeSet::new();
+        let mut consumed = 0;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut previous = -1;
+            for _ in 0..self.max_steps {
+                let ip = code.ip();
+                edges.insert((previous, ip));
+                previous = ip;
+                let reads = code.prog.get(ip as usize).is_some_and(|op| op % 100 == 3);
+                match code.step() {
+                    None | Some(Result::Output(_)) => {}
+                    Some(Result::NeedInput) | Some(Result::Halt) => return None,
+                    Some(Result::Error(e)) => return Some(Fault::Error(e)),
+                    Some(Result::Looping(_)) => return Some(Fault::Hang { ip }),
+                }
+                if reads {
+                    consumed += 1;
+                }
+            }
+            Some(Fault::Hang { ip: code.ip() })
+        }));
+        let fault = result.unwrap_or_else(|payload| {
+            let message = match payload.downcast_ref::<&str>() {
+                Some(s) => s.to_string(),
+                None => payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            Some(Fault::Panic {
+                ip: code.ip(),
+                message,
+            })
+        });
+        Run {
+            edges,
+            consumed,
+            fault,
+        }
+    }
+
+    fn save(&self, inputs: &[i64], fault: Option<&Fault>) {
+        let dir = match &self.dir {
+            Some(dir) => dir,
+            None => return,
+        };
+        let text = join(inputs);
+        let (path, contents) = match fault {
+            Some(fault) => {
+                let name = format!("{:016x}.txt", hash(&format!("{}\n{}", text, fault)));
+                (
+                    dir.join("crashes").join(name),
+                    format!("{}\n{}\n", text, fault),
+                )
+            }
+            None => (dir.join(format!("{:016x}.txt", hash(&text))), text + "\n"),
+        };
+        fs::write(&path, contents).unwrap_or_else(|e| panic!("Error writing {:?}: {}", path, e));
+    }
+
+    fn mutate(&mut self) -> Vec<i64> {
+        let i = self.below(self.corpus.len());
+        let mut inputs = self.corpus[i].clone();
+        for _ in 0..1 + self.below(4) {
+            let len = inputs.len();
+            match self.below(6) {
+                0 if len > 0 => {
+                    let i = self.below(len);
+                    inputs[i] = self.value();
+                }
+                1 if len > 0 => {
+                    let i = self.below(len);
+                    inputs[i] = inputs[i].wrapping_add(self.below(33) as i64 - 16);
+                }
+                2 => {
+                    let i = self.below(len + 1);
+                    let value = self.value();
+                    inputs.insert(i, value);
+                }
+                3 if len > 0 => {
+                    let i = self.below(len);
+                    inputs.remove(i);
+                }
+                4 => {
+                    // Splice with another entry
+                    let other = self.below(self.corpus.len());
+                    let from = self.below(self.corpus[other].len() + 1);
+                    inputs.truncate(self.below(len + 1));
+                    inputs.extend_from_slice(&self.corpus[other][from..]);
+                }
+                _ => {
+                    let value = self.value();
+                    inputs.push(value);
+                }
+            }
+        }
+        inputs.truncate(self.max_inputs);
+        inputs
+    }
+
+    fn value(&mut self) -> i64 {
+        match self.below(4) {
+            0 if !self.dictionary.is_empty() => {
+                let i = self.below(self.dictionary.len());
+                self.dictionary[i]
+            }
+            1 => match self.below(20) {
+                0 => 10,
+                _ => 32 + self.below(95) as i64,
+            },
+            2 => self.below(20) as i64 - 4,
+            _ => match self.below(4) {
+                0 => i64::MIN,
+                1 => i64::MAX,
+                _ => self.next() as i64,
+            },
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        // xorshift
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 7;
+        self.random ^= self.random << 17;
+        self.random
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn parse(s: &str) -> Option<Vec<i64>> {
+    match s.lines().next().unwrap_or("") {
+        "" => Some(Vec::new()),
+        line => line.split(',').map(|v| v.parse().ok()).collect(),
+    }
+}
+
+fn join(values: &[i64]) -> String {
+    let strings: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    strings.join(",")
+}
+
+/// FNV-1a, stable between runs for naming files.
+fn hash(s: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in s.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use crate::Overflow;
+    use std::env;
+
+    #[test]
+    fn test_finds_panic() {
+        // Jumps to the invalid instruction 98 if the input is 55
+        let code = Intcode::parse("3,11,1008,11,55,12,1005,12,10,99,98,0,0");
+        let mut fuzzer = Fuzzer::new(code, 1);
+        let crashes = fuzzer.fuzz(1000);
+        assert_eq!(crashes.len(), 1);
+        assert_eq!(crashes[0].inputs, vec![55]);
+        assert!(matches!(crashes[0].fault, Fault::Panic { ip: 10, .. }));
+        assert!(fuzzer.fuzz(1000).is_empty());
+        assert_eq!(fuzzer.addresses(), 5);
+    }
+
+    #[test]
+    fn test_finds_loop() {
+        // Jumps to itself forever if the input is 3, no step limit is needed to find that
+        let code = Intcode::parse("3,11,1008,11,3,12,1005,12,6,99,0,0,0");
+        let mut fuzzer = Fuzzer::new(code, 1);
+        fuzzer.max_steps(usize::MAX);
+        let crashes = fuzzer.fuzz(1000);
+        assert_eq!(crashes.len(), 1);
+        assert_eq!(crashes[0].inputs, vec![3]);
+        assert_eq!(crashes[0].fault, Fault::Hang { ip: 6 });
+    }
+
+    #[test]
+    fn test_finds_hidden_mode() {
+        let source = "
+            fn main() {
+                if input() == 1234 {
+                    if input() == -77 {
+                        output(secret(input()));
+                    }
+                }
+                output(0);
+            }
+
+            // Overflows for anything but 0
+            fn secret(x) {
+                return x * 3037000500 * 3037000500;
+            }
+        ";
+        let mut code = Intcode::new(compile(source).unwrap());
+        code.set_overflow(Overflow::Trap);
+
+        let dir = env::temp_dir().join(format!("intcode-coverage-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut fuzzer = Fuzzer::new(code.clone(), 7);
+        fuzzer.corpus_dir(&dir).unwrap();
+        let crashes = fuzzer.fuzz(20_000);
+        // Both multiplications can overflow, depending on the size of the last input
+        assert_eq!(crashes.len(), 2);
+        for crash in &crashes {
+            assert_eq!(crash.inputs[..2], [1234, -77]);
+            assert_ne!(crash.inputs[2], 0);
+            assert!(matches!(crash.fault, Fault::Error(Error::Overflow { .. })));
+        }
+
+        // The corpus was saved, so a new fuzzer starts with the same coverage
+        let mut resumed = Fuzzer::new(code, 7);
+        resumed.corpus_dir(&dir).unwrap();
+        assert_eq!(resumed.edges(), fuzzer.edges());
+        assert_eq!(fs::read_dir(dir.join("crashes")).unwrap().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
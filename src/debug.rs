@@ -0,0 +1,364 @@
+//! A debug server for Intcode, loosely modeled on the GDB remote protocol.
+//!
+//! Commands and replies are lines of text. Commands:
+//!
+//! * `s`: step one instruction
+//! * `c`: continue until a breakpoint, output, input is needed or halt, for at most
+//!   [`Debugger::max_steps`] instructions
+//! * `Z <address>`, `z <address>`: set or remove a breakpoint
+//! * `m <address> <count>`: read memory, replies with comma-separated values
+//! * `M <address> <value>,<value>,...`: write memory
+//!
+//!   Both are limited to the first [`MAX_CELLS`] cells, or the memory the program uses if that's
+//!   more.
+//! * `g`: read registers, replies with `<ip>,<relative base>`
+//! * `i <value>`: add input
+//! * `bt`: the call stack (see [`crate::callstack`]), the innermost function first, as
+//!   `<function>@<call site>` separated by commas, ending with `0` for the start of the program
+//! * `k`: close the connection
+//!
+//! Stepping and continuing reply with why the machine stopped: `T <ip>` (stepped or hit a
+//! breakpoint), `S <ip>` (ran for the maximum number of steps), `O <value>` (output), `I` (needs
+//! input), `W` (halted) or `E <message>` (error). Errors include instructions that the VM can't
+//! run, e.g. unknown opcodes, and ones that access memory beyond [`MAX_CELLS`] (or the memory the
+//! program uses if that's more); the machine stays at the instruction. Other commands reply with
+//! `OK` or `E <message>`.
+
+use crate::callstack::CallStack;
+use crate::{Instruction, Intcode, Result, Word};
+use std::any::Any;
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+
+/// Limit for the memory that `m`, `M` and the program can access, so that a client can't make
+/// the server allocate arbitrary amounts of memory.
+pub const MAX_CELLS: usize = 1 << 20;
+
+pub struct Debugger<W = i64> {
+    pub code: Intcode<W>,
+    breakpoints: BTreeSet<i64>,
+    calls: CallStack,
+    max_steps: usize,
+}
+
+impl<W: Word> Debugger<W> {
+    pub fn new(code: Intcode<W>) -> Self {
+        Debugger {
+            code,
+            breakpoints: BTreeSet::new(),
+            calls: CallStack::new(),
+            max_steps: 100_000_000,
+        }
+    }
+
+    /// Steps after which `c` stops, 100 000 000 by default.
+    pub fn max_steps(&mut self, max_steps: usize) -> &mut Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Accepts one client on `listener` and handles its commands until it disconnects.
+    pub fn serve(&mut self, listener: &TcpListener) -> io::Result<()> {
+        let (stream, _) = listener.accept()?;
+        self.serve_stream(stream)
+    }
+
+    pub fn serve_stream(&mut self, stream: TcpStream) -> io::Result<()> {
+        let mut writer = stream.try_clone()?;
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            if line.trim() == "k" {
+                break;
+            }
+            writeln!(writer, "{}", self.handle(&line))?;
+        }
+        Ok(())
+    }
+
+    /// Handles one command and returns the reply.
+    pub fn handle(&mut self, command: &str) -> String {
+        let mut parts = command.split_whitespace();
+        let name = parts.next().unwrap_or("");
+        let args: Vec<&str> = parts.collect();
+        let reply = match (name, args.as_slice()) {
+            ("s", []) => Ok(self.step()),
+            ("c", []) => Ok(self.cont()),
+            ("Z", [address]) => parse(address).map(|a| {
+                self.breakpoints.insert(a);
+                "OK".to_string()
+            }),
+            ("z", [address]) => parse(address).map(|a| {
+                self.breakpoints.remove(&a);
+                "OK".to_string()
+            }),
+            ("m", [address, count]) => parse::<usize>(address).and_then(|address| {
+                let count = parse::<usize>(count)?;
+                self.check_range(address, count)?;
+                let values: Vec<String> = (address..address + count)
+                    .map(|a| self.code.prog.get(a).cloned().unwrap_or_else(W::zero))
+                    .map(|v| v.to_string())
+                    .collect();
+                Ok(values.join(","))
+            }),
+            ("M", [address, values]) => parse::<usize>(address).and_then(|address| {
+                let values = values
+                    .split(',')
+                    .map(parse)
+                    .collect::<std::result::Result<Vec<W>, _>>()?;
+                let end = self.check_range(address, values.len())?;
+                if end > self.code.prog.len() {
+                    self.code.prog.resize(end, W::zero());
+                }
+                for (i, value) in values.into_iter().enumerate() {
+                    self.code.prog[address + i] = value;
+                }
+                Ok("OK".to_string())
+            }),
+            ("g", []) => Ok(format!("{},{}", self.code.ip(), self.code.relative_base())),
+            ("i", [value]) => parse(value).map(|v| {
+                self.code.add_input(v);
+                "OK".to_string()
+            }),
+            ("bt", []) => {
+                let mut frames: Vec<String> = self
+                    .calls
+                    .frames()
+                    .iter()
+                    .rev()
+                    .map(|frame| format!("{}@{}", frame.function, frame.call_site))
+                    .collect();
+                frames.push("0".to_string());
+                Ok(frames.join(","))
+            }
+            _ => Err(format!("Unknown command {:?}", command)),
+        };
+        reply.unwrap_or_else(|e| format!("E {}", e))
+    }
+
+    /// Returns the end of the range, or an error if it's beyond the memory that can be accessed.
+    fn check_range(&self, address: usize, count: usize) -> std::result::Result<usize, String> {
+        match address.checked_add(count) {
+            Some(end) if end <= self.code.prog.len().max(MAX_CELLS) => Ok(end),
+            _ => Err(format!("Range {}+{} out of bounds", address, count)),
+        }
+    }
+
+    fn step(&mut self) -> String {
+        match self.step_checked() {
+            Ok(Some(result)) => stop(result),
+            Ok(None) => format!("T {}", self.code.ip()),
+            Err(e) => format!("E {}", e),
+        }
+    }
+
+    fn cont(&mut self) -> String {
+        for _ in 0..self.max_steps {
+            match self.step_checked() {
+                Ok(Some(result)) => return stop(result),
+                Ok(None) => {}
+                Err(e) => return format!("E {}", e),
+            }
+            if self.breakpoints.contains(&self.code.ip()) {
+                return format!("T {}", self.code.ip());
+            }
+        }
+        format!("S {}", self.code.ip())
+    }
+
+    /// Runs one instruction, or returns an error instead of running it if the VM can't.
+    fn step_checked(&mut self) -> std::result::Result<Option<Result<W>>, String> {
+        self.check_addresses()?;
+        let (calls, code) = (&mut self.calls, &mut self.code);
+        panic::catch_unwind(AssertUnwindSafe(|| calls.step(code))).map_err(panic_message)
+    }
+
+    /// Checks that the next instruction doesn't access memory beyond the limit, which the VM would
+    /// allocate. Instructions added with [`Intcode::register_opcode`] aren't checked.
+    fn check_addresses(&self) -> std::result::Result<(), String> {
+        let code = &self.code;
+        let value = |address: i64| {
+            code.prog
+                .get(address as usize)
+                .and_then(|value| value.to_i64())
+                .unwrap_or(0)
+        };
+        let ip = code.ip();
+        let instruction = Instruction::decode(value(ip));
+        let mut addresses = vec![ip];
+        for i in 0..instruction.params().unwrap_or(0) {
+            let param = ip.saturating_add(1 + i as i64);
+            addresses.push(param);
+            match instruction.modes[i] {
+                0 => addresses.push(value(param)),
+                2 => addresses.push(code.relative_base().saturating_add(value(param))),
+                _ => {}
+            }
+        }
+        let limit = code.prog.len().max(MAX_CELLS) as i64;
+        match addresses.into_iter().find(|&address| address >= limit) {
+            Some(address) => Err(format!("Address {} out of bounds", address)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
+
+fn stop<W: Word>(result: Result<W>) -> String {
+    match result {
+        Result::Output(o) => format!("O {}", o),
+        Result::NeedInput => "I".to_string(),
+        Result::Halt => "W".to_string(),
+        Result::Error(e) => format!("E {}", e),
+        Result::Looping(cycle) => format!("E Looping with a cycle of {} instructions", cycle),
+    }
+}
+
+fn parse<T: FromStr>(s: &str) -> std::result::Result<T, String> {
+    s.parse().map_err(|_| format!("Invalid number {:?}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use std::thread;
+
+    #[test]
+    fn test_handle() {
+        // Adds input to 10 and outputs it
+        let code = Intcode::parse("3,9,1001,9,10,9,4,9,99,0");
+        let mut debugger = Debugger::new(code);
+        assert_eq!(debugger.handle("g"), "0,0");
+        assert_eq!(debugger.handle("s"), "I");
+        assert_eq!(debugger.handle("i 5"), "OK");
+        assert_eq!(debugger.handle("s"), "T 2");
+        assert_eq!(debugger.handle("m 9 2"), "5,0");
+        assert_eq!(debugger.handle("M 9 7"), "OK");
+        assert_eq!(debugger.handle("Z 6"), "OK");
+        assert_eq!(debugger.handle("c"), "T 6");
+        assert_eq!(debugger.handle("c"), "O 17");
+        assert_eq!(debugger.handle("c"), "W");
+        assert_eq!(debugger.handle("x"), "E Unknown command \"x\"");
+        assert_eq!(debugger.handle("m a 1"), "E Invalid number \"a\"");
+        assert_eq!(
+            debugger.handle(&format!("m 1 {}", usize::MAX)),
+            format!("E Range 1+{} out of bounds", usize::MAX)
+        );
+        assert_eq!(
+            debugger.handle(&format!("M {} 1", MAX_CELLS)),
+            format!("E Range {}+1 out of bounds", MAX_CELLS)
+        );
+        assert_eq!(debugger.handle(&format!("M {} 1", MAX_CELLS - 1)), "OK");
+        assert_eq!(debugger.code.prog.len(), MAX_CELLS);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut debugger = Debugger::new(Intcode::parse("1101,1,1,5,99,0"));
+        assert_eq!(debugger.handle("M 4 42"), "OK");
+        assert_eq!(debugger.handle("c"), "E not implemented: Unknown opcode 42");
+        assert_eq!(debugger.handle("g"), "4,0");
+        assert_eq!(debugger.handle("M 4 304"), "OK");
+        assert_eq!(debugger.handle("s"), "E Unknown mode 3 for param");
+        assert_eq!(debugger.handle("M 4 4,1000000000000"), "OK");
+        assert_eq!(
+            debugger.handle("s"),
+            "E Address 1000000000000 out of bounds"
+        );
+        assert_eq!(debugger.code.prog.len(), 6);
+        assert_eq!(debugger.handle("g"), "4,0");
+
+        // Jumps to itself
+        let mut debugger = Debugger::new(Intcode::parse("1105,1,0"));
+        debugger.max_steps(1000);
+        assert_eq!(debugger.handle("c"), "S 0");
+        debugger.code.detect_loops();
+        assert!(debugger.handle("c").starts_with("E Looping"));
+    }
+
+    #[test]
+    fn test_backtrace() {
+        let code = compile(
+            "
+            fn main() {
+                output(f(1));
+            }
+
+            fn f(n) {
+                return g(n) + 1;
+            }
+
+            fn g(n) {
+                return n * 2;
+            }
+            ",
+        )
+        .unwrap();
+        let mut debugger = Debugger::new(Intcode::new(code));
+        assert_eq!(debugger.handle("bt"), "0");
+        let g = loop {
+            debugger.handle("s");
+            if debugger.calls.frames().len() == 3 {
+                break debugger.calls.frames()[2].clone();
+            }
+        };
+        let frames = debugger.calls.frames().to_vec();
+        assert_eq!(
+            debugger.handle("bt"),
+            format!(
+                "{}@{},{}@{},{}@{},0",
+                g.function,
+                g.call_site,
+                frames[1].function,
+                frames[1].call_site,
+                frames[0].function,
+                frames[0].call_site
+            )
+        );
+        assert_eq!(debugger.handle("c"), "O 3");
+        assert_eq!(debugger.handle("c"), "W");
+        assert_eq!(debugger.handle("bt"), "0");
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let code = Intcode::parse(include_str!("../input/2019/day09.txt"));
+            let mut debugger = Debugger::new(code);
+            debugger.serve(&listener).unwrap();
+            (debugger.code.ip(), debugger.code.relative_base())
+        });
+
+        let stream = TcpStream::connect(address).unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request = |command: &str| {
+            writeln!(writer, "{}", command).unwrap();
+            let mut reply = String::new();
+            reader.read_line(&mut reply).unwrap();
+            reply.trim_end().to_string()
+        };
+        assert_eq!(request("i 1"), "OK");
+        assert_eq!(request("c"), "O 2955820355");
+        assert_eq!(request("c"), "W");
+        let registers = request("g");
+        request("k");
+
+        let (ip, relative_base) = server.join().unwrap();
+        assert_eq!(registers, format!("{},{}", ip, relative_base));
+    }
+}
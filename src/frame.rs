@@ -0,0 +1,180 @@
+//! Decoding outputs that come in fixed-size frames, e.g. `x, y, tile` in day 13 or
+//! `color, turn` in day 11.
+//!
+//! A type implementing [`Frame`] says how many outputs make a frame and how to decode them.
+//! [`Intcode::read_frame`] then either returns a whole frame or says why it couldn't: the program
+//! stopped between frames (e.g. needs input), or in the middle of one.
+
+use crate::{Intcode, Result, Word};
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+pub trait Frame<W = i64>: Sized {
+    /// Number of outputs in a frame.
+    const SIZE: usize;
+
+    /// Decodes a frame, `values` has `SIZE` items.
+    fn decode(values: Vec<W>) -> Self;
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum FrameError<W = i64> {
+    /// The program stopped between frames, with `NeedInput`, `Halt` or `Error`.
+    Stopped(Result<W>),
+    /// The program stopped after outputting only part of a frame.
+    Partial { values: Vec<W>, result: Result<W> },
+}
+
+impl<W: Word> fmt::Display for FrameError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Stopped(result) => write!(f, "Expected frame, got {:?}", result),
+            FrameError::Partial { values, result } => {
+                write!(f, "Partial frame {:?}, then got {:?}", values, result)
+            }
+        }
+    }
+}
+
+impl<W: Word> Intcode<W> {
+    pub fn read_frame<F: Frame<W>>(&mut self) -> core::result::Result<F, FrameError<W>> {
+        let mut values = Vec::with_capacity(F::SIZE);
+        while values.len() < F::SIZE {
+            match self.run() {
+                Result::Output(o) => values.push(o),
+                result if values.is_empty() => return Err(FrameError::Stopped(result)),
+                result => return Err(FrameError::Partial { values, result }),
+            }
+        }
+        Ok(F::decode(values))
+    }
+
+    /// Lazily reads frames until the program needs input or halts between frames. Partial frames
+    /// and errors are yielded as an `Err`, after which the iterator ends.
+    pub fn frames<F: Frame<W>>(&mut self) -> Frames<'_, F, W> {
+        Frames {
+            code: self,
+            done: false,
+            frame: PhantomData,
+        }
+    }
+}
+
+/// Iterator returned by [`Intcode::frames`].
+pub struct Frames<'a, F, W> {
+    code: &'a mut Intcode<W>,
+    done: bool,
+    frame: PhantomData<F>,
+}
+
+impl<'a, F: Frame<W>, W: Word> Iterator for Frames<'a, F, W> {
+    type Item = core::result::Result<F, FrameError<W>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.code.read_frame() {
+            Ok(frame) => Some(Ok(frame)),
+            Err(FrameError::Stopped(Result::NeedInput))
+            | Err(FrameError::Stopped(Result::Halt)) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<W> Frame<W> for (W, W) {
+    const SIZE: usize = 2;
+
+    fn decode(values: Vec<W>) -> Self {
+        let mut values = values.into_iter();
+        (values.next().unwrap(), values.next().unwrap())
+    }
+}
+
+impl<W> Frame<W> for (W, W, W) {
+    const SIZE: usize = 3;
+
+    fn decode(values: Vec<W>) -> Self {
+        let mut values = values.into_iter();
+        (
+            values.next().unwrap(),
+            values.next().unwrap(),
+            values.next().unwrap(),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Tile {
+    pub x: i64,
+    pub y: i64,
+    pub id: i64,
+}
+
+/// Output of the arcade cabinet in day 13: a tile to draw, or the score if `x` is -1 and `y` is
+/// 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Screen {
+    Tile(Tile),
+    Score(i64),
+}
+
+impl Frame for Screen {
+    const SIZE: usize = 3;
+
+    fn decode(values: Vec<i64>) -> Self {
+        match values[..] {
+            [-1, 0, score] => Screen::Score(score),
+            [x, y, id] => Screen::Tile(Tile { x, y, id }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_frame() {
+        // Outputs a pair, then needs input, then outputs half a pair
+        let mut code = Intcode::parse("104,1,104,2,3,9,104,3,99,0");
+        assert_eq!(code.read_frame::<(i64, i64)>(), Ok((1, 2)));
+        assert_eq!(
+            code.read_frame::<(i64, i64)>(),
+            Err(FrameError::Stopped(Result::NeedInput))
+        );
+        code.add_input(0);
+        assert_eq!(
+            code.read_frame::<(i64, i64)>(),
+            Err(FrameError::Partial {
+                values: vec![3],
+                result: Result::Halt
+            })
+        );
+    }
+
+    #[test]
+    fn test_day13_screen() {
+        let mut code = Intcode::parse(include_str!("../input/2019/day13.txt"));
+        let screens: Vec<Screen> = code.frames().map(|s| s.unwrap()).collect();
+        let blocks = screens
+            .iter()
+            .filter(|s| matches!(s, Screen::Tile(Tile { id: 2, .. })))
+            .count();
+        assert_eq!(blocks, 380);
+
+        let mut code = Intcode::parse(include_str!("../input/2019/day13.txt"));
+        code.prog[0] = 2;
+        let screens: Vec<Screen> = code.frames().map(|s| s.unwrap()).collect();
+        assert_eq!(screens.last(), Some(&Screen::Score(0)));
+    }
+}
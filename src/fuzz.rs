@@ -0,0 +1,522 @@
+//! Differential fuzzing of Intcode implementations.
+//!
+//! Generates random programs that only use the opcodes all implementations support, runs them
+//! on each implementation and compares what they report. A case where they disagree is shrunk
+//! to a smaller one that still disagrees.
+//!
+//! Generated programs only jump forward, so they always halt, and only access cells within the
+//! program. The values they compute stay within [`Config::max_value`], so implementations with
+//! a smaller word type (e.g. `i32` in day 5) can be compared too.
+
+use crate::{Intcode, Result};
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+/// What an implementation reports after running a program. Fields that an implementation can't
+/// observe are `None`, and aren't compared.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Outcome {
+    pub panicked: bool,
+    pub outputs: Option<Vec<i64>>,
+    /// Last output, or 0 if there wasn't any
+    pub last_output: Option<i64>,
+    /// Final memory, can be only the start of it
+    pub memory: Option<Vec<i64>>,
+}
+
+impl Outcome {
+    fn agrees(&self, other: &Outcome) -> bool {
+        fn same<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+        }
+        let memory = match (&self.memory, &other.memory) {
+            (Some(a), Some(b)) => {
+                let len = a.len().min(b.len());
+                a[..len] == b[..len]
+            }
+            _ => true,
+        };
+        self.panicked == other.panicked
+            && same(&self.outputs, &other.outputs)
+            && same(&self.last_output, &other.last_output)
+            && memory
+    }
+}
+
+pub struct Implementation<'a> {
+    pub name: &'a str,
+    /// Supported opcodes besides 99
+    pub opcodes: &'a [i64],
+    /// Whether immediate mode is supported, otherwise all parameters are positions
+    pub modes: bool,
+    /// Runs a program with the input. Programs that read input more than once get the same input
+    /// every time.
+    pub run: &'a dyn Fn(&[i64], i64) -> Outcome,
+}
+
+/// Runs `prog` with [`Intcode`].
+pub fn intcode(prog: &[i64], input: i64) -> Outcome {
+    intcode_machine(Intcode::new(prog.to_vec()), input)
+}
+
+/// Like [`intcode`], for a machine that was set up differently, e.g. with the cache enabled.
+pub fn intcode_machine(mut code: Intcode, input: i64) -> Outcome {
+    let len = code.prog.len();
+    for _ in 0..len {
+        code.add_input(input);
+    }
+    let mut outputs = Vec::new();
+    let mut panicked = false;
+    // Generated programs can't run longer than this, so just to be safe
+    for _ in 0..len {
+        match code.run() {
+            Result::Output(o) => outputs.push(o),
+            Result::Halt => break,
+            _ => {
+                panicked = true;
+                break;
+            }
+        }
+    }
+    Outcome {
+        panicked,
+        last_output: Some(outputs.last().cloned().unwrap_or(0)),
+        outputs: Some(outputs),
+        memory: Some(code.prog),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub seed: u64,
+    pub cases: usize,
+    /// Maximum number of instructions per program
+    pub max_instructions: usize,
+    /// Maximum absolute value of any value the program computes
+    pub max_value: i64,
+    /// Whether values can be negative, e.g. day 2 only works with `usize`
+    pub negative: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            seed: 0x2545_f491_4f6c_dd1d,
+            cases: 500,
+            max_instructions: 20,
+            max_value: i64::from(i32::MAX),
+            negative: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Failure {
+    pub prog: Vec<i64>,
+    pub input: i64,
+    /// Outcome for each implementation, by name
+    pub outcomes: Vec<(String, Outcome)>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operand {
+    Immediate(i64),
+    Slot(usize),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Op {
+    /// Add, multiply, less than or equals into a slot
+    Arithmetic(i64, Operand, Operand, usize),
+    Input(usize),
+    Output(Operand),
+    /// Jump if true or false to the op with the index
+    Jump(i64, Operand, usize),
+}
+
+/// A program as ops on slots (data cells after the code), so that it can be shrunk without
+/// breaking addresses.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Case {
+    ops: Vec<Op>,
+    slots: Vec<i64>,
+    input: i64,
+}
+
+impl Case {
+    fn assemble(&self) -> Vec<i64> {
+        let len = |op: &Op| match op {
+            Op::Arithmetic(..) => 4,
+            Op::Input(_) | Op::Output(_) => 2,
+            Op::Jump(..) => 3,
+        };
+        let mut addresses = Vec::new();
+        let mut address = 0;
+        for op in &self.ops {
+            addresses.push(address);
+            address += len(op);
+        }
+        // At the end, copy the first slot to address 0 so that it's in memory for day 2
+        addresses.push(address);
+        let data = address + 4 + 1;
+
+        let mut prog = Vec::new();
+        let mut instruction = |opcode: i64, operands: &[Operand]| {
+            let mut modes = 0;
+            let mut values = Vec::new();
+            for (i, operand) in operands.iter().enumerate() {
+                match *operand {
+                    Operand::Immediate(v) => {
+                        modes += 10i64.pow(i as u32 + 2);
+                        values.push(v);
+                    }
+                    Operand::Slot(s) => values.push((data + s) as i64),
+                }
+            }
+            prog.push(opcode + modes);
+            prog.extend(values);
+        };
+        for op in &self.ops {
+            match *op {
+                Op::Arithmetic(opcode, a, b, target) => {
+                    instruction(opcode, &[a, b, Operand::Slot(target)])
+                }
+                Op::Input(target) => instruction(3, &[Operand::Slot(target)]),
+                Op::Output(a) => instruction(4, &[a]),
+                Op::Jump(opcode, condition, target) => instruction(
+                    opcode,
+                    &[condition, Operand::Immediate(addresses[target] as i64)],
+                ),
+            }
+        }
+        prog.extend(&[1, data as i64, data as i64 + 1, 0, 99]);
+        prog.extend(&self.slots);
+        prog
+    }
+
+    /// Smaller variants of the case, for shrinking.
+    fn shrink(&self) -> Vec<Case> {
+        let mut cases = Vec::new();
+        for i in 0..self.ops.len() {
+            let mut case = self.clone();
+            case.ops.remove(i);
+            for op in &mut case.ops {
+                if let Op::Jump(_, _, target) = op {
+                    if *target > i {
+                        *target -= 1;
+                    }
+                }
+            }
+            // A jump can't go backwards after removing
+            let valid = case.ops.iter().enumerate().all(|(j, op)| match op {
+                Op::Jump(_, _, target) => *target > j,
+                _ => true,
+            });
+            if valid {
+                cases.push(case);
+            }
+        }
+        for i in 0..self.slots.len() {
+            if self.slots[i] != 0 {
+                let mut case = self.clone();
+                case.slots[i] /= 2;
+                cases.push(case);
+            }
+        }
+        for i in 0..self.ops.len() {
+            let mut case = self.clone();
+            let operand = match &mut case.ops[i] {
+                Op::Arithmetic(_, Operand::Immediate(v), _, _) if *v != 0 => v,
+                Op::Arithmetic(_, _, Operand::Immediate(v), _) if *v != 0 => v,
+                Op::Output(Operand::Immediate(v)) if *v != 0 => v,
+                Op::Jump(_, Operand::Immediate(v), _) if *v != 0 => v,
+                _ => continue,
+            };
+            *operand /= 2;
+            cases.push(case);
+        }
+        if self.input != 0 {
+            cases.push(Case {
+                input: self.input / 2,
+                ..self.clone()
+            });
+        }
+        cases
+    }
+}
+
+struct Generator {
+    random: u64,
+    config: Config,
+    opcodes: Vec<i64>,
+    modes: bool,
+}
+
+impl Generator {
+    fn next(&mut self) -> u64 {
+        // xorshift
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 7;
+        self.random ^= self.random << 17;
+        self.random
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn value(&mut self, max: i64) -> i64 {
+        let value = (self.next() % (max as u64 + 1)) as i64;
+        if self.config.negative && self.next() & 1 == 0 {
+            -value
+        } else {
+            value
+        }
+    }
+
+    fn case(&mut self) -> Case {
+        let slot_count = 2 + self.below(4);
+        let mut slots: Vec<i64> = (0..slot_count).map(|_| self.value(100)).collect();
+        // Slot 1 stays zero for copying slot 0 to address 0 at the end
+        slots[1] = 0;
+        let input = self.value(100);
+        // Biggest absolute value each slot can have, to stay within max_value
+        let mut bounds: Vec<i64> = slots.iter().map(|v| v.abs()).collect();
+
+        let count = self.below(self.config.max_instructions + 1);
+        let mut ops = Vec::new();
+        let mut jumps = Vec::new();
+        for i in 0..count {
+            let index = self.below(self.opcodes.len());
+            let opcode = self.opcodes[index];
+            let op = match opcode {
+                1 | 2 | 7 | 8 => {
+                    let a = self.operand(slot_count);
+                    let b = self.operand(slot_count);
+                    let bound = |operand: Operand| match operand {
+                        Operand::Immediate(v) => v.abs(),
+                        Operand::Slot(s) => bounds[s],
+                    };
+                    let result = match opcode {
+                        1 => bound(a).saturating_add(bound(b)),
+                        2 => bound(a).saturating_mul(bound(b)),
+                        _ => 1,
+                    };
+                    if result > self.config.max_value {
+                        continue;
+                    }
+                    let target = self.slot_except_zero(slot_count);
+                    bounds[target] = bounds[target].max(result);
+                    Op::Arithmetic(opcode, a, b, target)
+                }
+                3 => {
+                    let target = self.slot_except_zero(slot_count);
+                    bounds[target] = bounds[target].max(input.abs());
+                    Op::Input(target)
+                }
+                4 => Op::Output(self.operand(slot_count)),
+                5 | 6 => {
+                    let condition = self.operand(slot_count);
+                    jumps.push(ops.len());
+                    Op::Jump(opcode, condition, i + 1)
+                }
+                _ => continue,
+            };
+            ops.push(op);
+        }
+        // Now that we know how many ops there are, pick forward targets (can be the end)
+        for j in jumps {
+            let target = j + 1 + self.below(ops.len() - j);
+            if let Op::Jump(_, _, t) = &mut ops[j] {
+                *t = target;
+            }
+        }
+        Case { ops, slots, input }
+    }
+
+    fn slot_except_zero(&mut self, slot_count: usize) -> usize {
+        let slot = self.below(slot_count - 1);
+        if slot >= 1 {
+            slot + 1
+        } else {
+            slot
+        }
+    }
+
+    fn operand(&mut self, slot_count: usize) -> Operand {
+        if self.modes && self.next() & 1 == 0 {
+            Operand::Immediate(self.value(100))
+        } else {
+            Operand::Slot(self.below(slot_count))
+        }
+    }
+}
+
+fn run(implementation: &Implementation, prog: &[i64], input: i64) -> Outcome {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (implementation.run)(prog, input)));
+    result.unwrap_or_else(|_| Outcome {
+        panicked: true,
+        ..Outcome::default()
+    })
+}
+
+fn check(implementations: &[Implementation], case: &Case) -> Option<Failure> {
+    let prog = case.assemble();
+    let outcomes: Vec<(String, Outcome)> = implementations
+        .iter()
+        .map(|i| (i.name.to_string(), run(i, &prog, case.input)))
+        .collect();
+    if outcomes.iter().all(|(_, o)| o.agrees(&outcomes[0].1)) {
+        None
+    } else {
+        Some(Failure {
+            prog,
+            input: case.input,
+            outcomes,
+        })
+    }
+}
+
+/// Runs random programs on all implementations, returns the smallest failure that was found for
+/// the first case where they disagree.
+pub fn differential(implementations: &[Implementation], config: Config) -> Option<Failure> {
+    let opcodes: Vec<i64> = (1..=8)
+        .filter(|op| implementations.iter().all(|i| i.opcodes.contains(op)))
+        .collect();
+    let mut generator = Generator {
+        random: config.seed.max(1),
+        config,
+        opcodes,
+        modes: implementations.iter().all(|i| i.modes),
+    };
+    if generator.opcodes.is_empty() {
+        return None;
+    }
+
+    for _ in 0..config.cases {
+        let mut case = generator.case();
+        let mut failure = match check(implementations, &case) {
+            Some(failure) => failure,
+            None => continue,
+        };
+
+        // Shrink until none of the smaller variants fail anymore
+        quietly(|| {
+            while let Some((smaller, smaller_failure)) = case
+                .shrink()
+                .into_iter()
+                .find_map(|c| check(implementations, &c).map(|f| (c, f)))
+            {
+                case = smaller;
+                failure = smaller_failure;
+            }
+        });
+        return Some(failure);
+    }
+    None
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without printing the panics that are caught on this thread. The panic hook is only
+/// replaced once, with one that checks a thread local, so that panics on other threads (e.g.
+/// tests running in parallel) are still printed.
+pub(crate) fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            QUIET.with(|quiet| quiet.set(self.0));
+        }
+    }
+    let _restore = Restore(QUIET.with(|quiet| quiet.replace(true)));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Instruction;
+
+    const ALL: &[i64] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+    #[test]
+    fn test_intcode_with_cache() {
+        let cached = |prog: &[i64], input: i64| {
+            let mut code = Intcode::new(prog.to_vec());
+            code.enable_cache();
+            intcode_machine(code, input)
+        };
+        let implementations = [
+            Implementation {
+                name: "intcode",
+                opcodes: ALL,
+                modes: true,
+                run: &intcode,
+            },
+            Implementation {
+                name: "cached",
+                opcodes: ALL,
+                modes: true,
+                run: &cached,
+            },
+        ];
+        assert!(differential(&implementations, Config::default()).is_none());
+    }
+
+    #[test]
+    fn test_finds_and_shrinks() {
+        // Gets less than wrong by outputting 1 for equal values too
+        let broken = |prog: &[i64], input: i64| {
+            let mut code = Intcode::new(prog.to_vec());
+            code.register_opcode(17, 3, |operands| {
+                let result = (operands.get(0) <= operands.get(1)) as i64;
+                operands.set(2, result);
+                None
+            });
+            // Generated programs have all instructions before the data
+            let mut ip = 0;
+            while code.prog[ip] != 99 {
+                let instruction = Instruction::decode(code.prog[ip]);
+                if instruction.opcode == 7 {
+                    code.prog[ip] += 10;
+                }
+                ip += instruction.params().unwrap() + 1;
+            }
+            intcode_machine(code, input)
+        };
+        let implementations = [
+            Implementation {
+                name: "intcode",
+                opcodes: ALL,
+                modes: true,
+                run: &intcode,
+            },
+            Implementation {
+                name: "broken",
+                opcodes: ALL,
+                modes: true,
+                run: &broken,
+            },
+        ];
+        let failure = differential(&implementations, Config::default()).unwrap();
+        // A less than on equal values, copying to address 0, halt and the slots
+        assert!(failure.prog.len() <= 4 + 5 + 6, "{:?}", failure);
+        assert_eq!(failure.prog[0] % 100, 7, "{:?}", failure);
+    }
+}
@@ -1,22 +1,355 @@
-use std::collections::LinkedList;
+//! The Intcode VM and tools around it.
+//!
+//! The VM itself ([`Intcode`], [`Result`], memory and instruction decoding) only needs `alloc`,
+//! so the crate builds with `#![no_std]` when the default `std` feature is disabled. Modules that
+//! need the standard library (threads, files, sockets, catching panics) and `BigInt` words are
+//! only available with the `std` feature.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, LinkedList};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use num::BigInt;
+use num::{ToPrimitive, Zero};
+
+pub mod affine;
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod binary;
+pub mod callstack;
+#[cfg(any(feature = "std", test))]
+pub mod compiled;
+pub mod compiler;
+#[cfg(feature = "std")]
+pub mod coverage;
+#[cfg(feature = "std")]
+pub mod debug;
+pub mod frame;
+#[cfg(feature = "std")]
+pub mod fuzz;
+pub mod optimize;
+#[cfg(feature = "std")]
+pub mod oracle;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod symbolic;
+pub mod taint;
+#[cfg(feature = "std")]
+pub mod trace;
+pub mod transpile;
+
+/// An Intcode machine with memory cells of type `W`, see [`Word`].
+#[derive(Clone)]
+pub struct Intcode<W = i64> {
+    pub prog: Vec<W>,
+    ip: i64,
+    inputs: LinkedList<W>,
+    relative_base: i64,
+    decoded: Option<Arc<Vec<Option<Instruction>>>>,
+    overflow: Overflow,
+    opcodes: Arc<BTreeMap<i64, Opcode<W>>>,
+    observers: Option<Arc<Observers<W>>>,
+    loops: Option<Box<LoopDetector<W>>>,
+    taint: Option<Box<taint::Taint>>,
+}
+
+type Handler<W> = dyn Fn(&mut Operands<W>) -> Option<Result<W>> + Send + Sync;
+
+/// An opcode registered with [`Intcode::register_opcode`].
+#[derive(Clone)]
+struct Opcode<W> {
+    params: usize,
+    handler: Arc<Handler<W>>,
+}
+
+type StepHook = dyn Fn(i64, Instruction) + Send + Sync;
+type WriteHook<W> = dyn Fn(i64, &W) + Send + Sync;
+type ValueHook<W> = dyn Fn(&W) + Send + Sync;
+
+/// Callbacks registered with [`Intcode::on_step`] and friends. Only allocated once one is
+/// registered, so that machines without observers only pay for checking the `Option`.
+#[derive(Clone)]
+struct Observers<W> {
+    step: Vec<Arc<StepHook>>,
+    write: Vec<Arc<WriteHook<W>>>,
+    input: Vec<Arc<ValueHook<W>>>,
+    output: Vec<Arc<ValueHook<W>>>,
+}
+
+/// State for [`Intcode::detect_loops`]. Counts steps since the last input or output, and hashes
+/// the state at backward jumps. When a hash was seen before, the state is kept as a candidate and
+/// compared exactly when the same number of steps passed again.
+#[derive(Clone)]
+struct LoopDetector<W> {
+    steps: u64,
+    seen: BTreeMap<u64, u64>,
+    candidate: Option<Candidate<W>>,
+}
 
 #[derive(Clone)]
-pub struct Intcode {
-    pub prog: Vec<i64>,
+struct Candidate<W> {
+    steps: u64,
+    cycle: u64,
     ip: i64,
-    inputs: LinkedList<i64>,
     relative_base: i64,
+    prog: Vec<W>,
+}
+
+/// FNV-1a, because `DefaultHasher` needs `std`.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Gives the handler of a registered opcode access to its parameters and the machine.
+pub struct Operands<'a, W> {
+    code: &'a mut Intcode<W>,
+    modes: [i64; 3],
+    params: usize,
+    jumped: bool,
+}
+
+impl<'a, W: Word> Operands<'a, W> {
+    /// Reads parameter `i` (counting from 0) according to its mode.
+    pub fn get(&mut self, i: usize) -> W {
+        self.check(i);
+        let ip = self.code.ip;
+        self.code.param(ip + 1 + i as i64, self.modes[i])
+    }
+
+    /// Writes to the address of parameter `i` (counting from 0) according to its mode.
+    pub fn set(&mut self, i: usize, value: W) {
+        self.check(i);
+        let ip = self.code.ip;
+        self.code.store(ip + 1 + i as i64, self.modes[i], value);
+    }
+
+    fn check(&self, i: usize) {
+        assert!(
+            i < self.params,
+            "Parameter {} is out of range, the opcode has {} parameters",
+            i,
+            self.params
+        );
+    }
+
+    pub fn input(&mut self) -> Option<W> {
+        self.code.next_input()
+    }
+
+    pub fn ip(&self) -> i64 {
+        self.code.ip
+    }
+
+    /// Continues at `target` instead of the next instruction.
+    pub fn jump(&mut self, target: i64) {
+        self.code.ip = target;
+        self.jumped = true;
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub enum Result {
-    Output(i64),
+pub enum Result<W = i64> {
+    Output(W),
     NeedInput,
     Halt,
+    Error(Error),
+    /// With [`Intcode::detect_loops`], the program got back to the same state without input or
+    /// output, so it would run forever. Contains the number of instructions in the cycle.
+    Looping(u64),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// An add or multiply at `ip` overflowed with [`Overflow::Trap`]
+    Overflow { ip: i64 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Overflow { ip } => write!(f, "Overflow at ip {}", ip),
+        }
+    }
+}
+
+/// What happens when an add or multiply overflows. The default is to wrap, regardless of
+/// whether overflow checks are enabled for the build.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Overflow {
+    Wrap,
+    /// Stop with [`Error::Overflow`]
+    Trap,
+    Saturate,
+}
+
+impl Overflow {
+    /// Returns `None` if the result overflowed with `Trap`.
+    pub fn add<W: Word>(self, a: W, b: W) -> Option<W> {
+        a.add_with(&b, self)
+    }
+
+    /// Returns `None` if the result overflowed with `Trap`.
+    pub fn mul<W: Word>(self, a: W, b: W) -> Option<W> {
+        a.mul_with(&b, self)
+    }
+}
+
+/// Type of the memory cells, inputs and outputs of a machine. `i64` is enough for the puzzles,
+/// `BigInt` (with the `std` feature) can be used for programs that compute larger numbers.
+///
+/// Addresses, opcodes and the relative base are always `i64`, a program that uses a value that
+/// doesn't fit for those panics.
+pub trait Word:
+    Clone + fmt::Debug + fmt::Display + Hash + Ord + FromStr + From<i64> + ToPrimitive + Zero
+{
+    /// Returns `None` if the result overflowed with [`Overflow::Trap`].
+    fn add_with(&self, other: &Self, overflow: Overflow) -> Option<Self>;
+
+    /// Returns `None` if the result overflowed with [`Overflow::Trap`].
+    fn mul_with(&self, other: &Self, overflow: Overflow) -> Option<Self>;
+}
+
+impl Word for i64 {
+    fn add_with(&self, other: &Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Wrap => Some(self.wrapping_add(*other)),
+            Overflow::Trap => self.checked_add(*other),
+            Overflow::Saturate => Some(self.saturating_add(*other)),
+        }
+    }
+
+    fn mul_with(&self, other: &Self, overflow: Overflow) -> Option<Self> {
+        match overflow {
+            Overflow::Wrap => Some(self.wrapping_mul(*other)),
+            Overflow::Trap => self.checked_mul(*other),
+            Overflow::Saturate => Some(self.saturating_mul(*other)),
+        }
+    }
+}
+
+/// Never overflows, so the overflow policy doesn't matter.
+#[cfg(feature = "std")]
+impl Word for BigInt {
+    fn add_with(&self, other: &Self, _: Overflow) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn mul_with(&self, other: &Self, _: Overflow) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+/// Converts a value that is used as an address or jump target.
+fn address<W: Word>(value: &W) -> i64 {
+    value
+        .to_i64()
+        .unwrap_or_else(|| panic!("Address {} out of range", value))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    pub opcode: i64,
+    pub modes: [i64; 3],
+}
+
+impl Instruction {
+    pub fn decode(instruction: i64) -> Self {
+        let opcode = instruction % 100;
+        let mut modes = instruction / 100;
+        let mode1 = modes % 10;
+        modes /= 10;
+        let mode2 = modes % 10;
+        modes /= 10;
+        let mode3 = modes % 10;
+        Instruction {
+            opcode,
+            modes: [mode1, mode2, mode3],
+        }
+    }
+
+    /// Number of parameters of the instruction, `None` if the opcode is unknown.
+    pub fn params(&self) -> Option<usize> {
+        match self.opcode {
+            99 => Some(0),
+            3 | 4 | 9 => Some(1),
+            5 | 6 => Some(2),
+            1 | 2 | 7 | 8 => Some(3),
+            _ => None,
+        }
+    }
 }
 
 impl Intcode {
     pub fn parse(instructions: &str) -> Self {
+        Self::parse_words(instructions)
+    }
+
+    pub fn new(prog: Vec<i64>) -> Self {
+        Self::from_words(prog)
+    }
+
+    /// Reads a program in the format of [`binary`], restoring the machine state if it has a
+    /// snapshot.
+    pub fn from_binary(bytes: &[u8]) -> core::result::Result<Self, binary::Error> {
+        let program = binary::Program::decode(bytes)?;
+        let mut code = Self::new(program.memory);
+        if let Some(snapshot) = program.snapshot {
+            code.ip = snapshot.ip;
+            code.relative_base = snapshot.relative_base;
+            code.inputs = snapshot.inputs.into_iter().collect();
+        }
+        Ok(code)
+    }
+
+    /// Writes the memory in the format of [`binary`], with a snapshot if the machine already ran
+    /// or has inputs.
+    pub fn to_binary(&self) -> Vec<u8> {
+        let snapshot = if self.ip != 0 || self.relative_base != 0 || !self.inputs.is_empty() {
+            Some(binary::Snapshot {
+                ip: self.ip,
+                relative_base: self.relative_base,
+                inputs: self.inputs.iter().cloned().collect(),
+            })
+        } else {
+            None
+        };
+        binary::Program {
+            memory: self.prog.clone(),
+            symbols: Vec::new(),
+            snapshot,
+        }
+        .encode()
+    }
+}
+
+impl<W: Word> Intcode<W> {
+    /// Like [`Intcode::parse`], for other word types, e.g. `Intcode::<BigInt>::parse_words`.
+    pub fn parse_words(instructions: &str) -> Self {
         let prog = instructions
             .trim()
             .split(',')
@@ -26,32 +359,152 @@ impl Intcode {
                     .unwrap_or_else(|_| panic!("Error parsing {:?}", s))
             })
             .collect();
-        Self::new(prog)
+        Self::from_words(prog)
     }
 
-    pub fn new(prog: Vec<i64>) -> Self {
+    pub fn from_words(prog: Vec<W>) -> Self {
         Self {
             prog,
             ip: 0,
             inputs: LinkedList::new(),
             relative_base: 0,
+            decoded: None,
+            overflow: Overflow::Wrap,
+            opcodes: Arc::new(BTreeMap::new()),
+            observers: None,
+            loops: None,
+            taint: None,
         }
     }
 
-    pub fn add_input(&mut self, input: i64) -> &mut Self {
+    /// Adds an opcode (1 to 99) with `params` parameters (at most 3) that runs `handler`, e.g. for
+    /// prototyping instructions that aren't part of Intcode.
+    ///
+    /// After the handler returns `None` or an output, the machine continues with the next
+    /// instruction (unless the handler jumped). For other results, it stays at the instruction,
+    /// e.g. to run it again after adding input.
+    pub fn register_opcode(
+        &mut self,
+        opcode: i64,
+        params: usize,
+        handler: impl Fn(&mut Operands<W>) -> Option<Result<W>> + Send + Sync + 'static,
+    ) -> &mut Self {
+        // Instructions are decoded with the modes in the digits above the opcode
+        assert!(
+            0 < opcode && opcode < 100,
+            "Opcode {} is not between 1 and 99",
+            opcode
+        );
+        assert!(
+            Instruction::decode(opcode).params().is_none(),
+            "Opcode {} is already defined",
+            opcode
+        );
+        assert!(params <= 3, "At most 3 parameters are supported");
+        let opcode_handler = Opcode {
+            params,
+            handler: Arc::new(handler),
+        };
+        Arc::make_mut(&mut self.opcodes).insert(opcode, opcode_handler);
+        self
+    }
+
+    /// Calls `f` with the address and decoded instruction before running each instruction. Note
+    /// that this and the other observers are shared between clones of the machine.
+    pub fn on_step(&mut self, f: impl Fn(i64, Instruction) + Send + Sync + 'static) -> &mut Self {
+        self.observers().step.push(Arc::new(f));
+        self
+    }
+
+    /// Calls `f` with the address and value of each memory write by the program.
+    pub fn on_write(&mut self, f: impl Fn(i64, &W) + Send + Sync + 'static) -> &mut Self {
+        self.observers().write.push(Arc::new(f));
+        self
+    }
+
+    /// Calls `f` with each input that the program consumes.
+    pub fn on_input(&mut self, f: impl Fn(&W) + Send + Sync + 'static) -> &mut Self {
+        self.observers().input.push(Arc::new(f));
+        self
+    }
+
+    /// Calls `f` with each output of the program.
+    pub fn on_output(&mut self, f: impl Fn(&W) + Send + Sync + 'static) -> &mut Self {
+        self.observers().output.push(Arc::new(f));
+        self
+    }
+
+    fn observers(&mut self) -> &mut Observers<W> {
+        let observers = self.observers.get_or_insert_with(|| {
+            Arc::new(Observers {
+                step: Vec::new(),
+                write: Vec::new(),
+                input: Vec::new(),
+                output: Vec::new(),
+            })
+        });
+        Arc::make_mut(observers)
+    }
+
+    /// Makes `run` return [`Result::Looping`] when the program would loop forever without input
+    /// or output. Costs a hash of the memory at each backward jump.
+    pub fn detect_loops(&mut self) -> &mut Self {
+        self.loops = Some(Box::new(LoopDetector {
+            steps: 0,
+            seen: BTreeMap::new(),
+            candidate: None,
+        }));
+        self
+    }
+
+    /// Tracks which inputs each value depends on, see [`taint`]. Costs working out the labels of
+    /// every instruction, so only for analysis.
+    pub fn track_taint(&mut self) -> &mut Self {
+        self.taint = Some(Box::new(taint::Taint::new(self)));
+        self
+    }
+
+    /// The labels tracked since [`Intcode::track_taint`] was called, e.g. `outputs()` for the
+    /// inputs that each output depends on.
+    pub fn taint(&self) -> Option<&taint::Taint> {
+        self.taint.as_deref()
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) -> &mut Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Decode instructions only once instead of on every step, which makes running faster.
+    ///
+    /// When the program writes to an instruction, its entry is dropped and decoded again the next
+    /// time it runs. The cache is shared between clones until one of them writes to an
+    /// instruction, so it's best to enable it on a prototype before cloning. Changes to
+    /// [`Intcode::prog`] from outside the program aren't noticed, so make them before enabling it.
+    pub fn enable_cache(&mut self) -> &mut Self {
+        let decoded = self.prog.iter().map(cache_entry).collect();
+        self.decoded = Some(Arc::new(decoded));
+        self
+    }
+
+    pub fn add_input(&mut self, input: W) -> &mut Self {
         self.inputs.push_back(input);
         self
     }
 
-    pub fn run_last(&mut self) -> i64 {
-        let mut output = 0;
+    pub fn run_last(&mut self) -> W {
+        let mut output = W::zero();
         while let Result::Output(o) = self.run() {
             output = o;
         }
         output
     }
 
-    pub fn run_all(&mut self) -> Vec<i64> {
+    pub fn run_all(&mut self) -> Vec<W> {
         let mut outputs = Vec::new();
         while let Result::Output(o) = self.run() {
             outputs.push(o);
@@ -59,114 +512,321 @@ impl Intcode {
         outputs
     }
 
-    pub fn run_expect_output(&mut self) -> i64 {
+    /// Lazily yields outputs until the program needs input or halts.
+    pub fn outputs(&mut self) -> Outputs<'_, W> {
+        Outputs { code: self }
+    }
+
+    /// Lazily yields outputs, taking the next item of `inputs` whenever the program needs input.
+    /// Stops when the program halts or needs input after `inputs` ran out.
+    pub fn feed<I: IntoIterator<Item = W>>(&mut self, inputs: I) -> Feed<'_, W, I::IntoIter> {
+        Feed {
+            code: self,
+            inputs: inputs.into_iter(),
+        }
+    }
+
+    /// Lazily yields outputs in groups of `n`, e.g. `x, y, tile` in day 13. Outputs that don't
+    /// make a complete group at the end are dropped.
+    pub fn chunks(&mut self, n: usize) -> Chunks<'_, W> {
+        assert!(n > 0, "Chunk size must be positive");
+        Chunks { code: self, n }
+    }
+
+    pub fn run_expect_output(&mut self) -> W {
         match self.run() {
             Result::Output(o) => o,
             result => panic!("Expected output, got {:?}", result),
         }
     }
 
-    pub fn run(&mut self) -> Result {
+    pub fn run(&mut self) -> Result<W> {
         loop {
-            let instruction = self.get(self.ip);
-            let op = instruction % 100;
-            let mut modes = instruction / 100;
-            let mode1 = modes % 10;
-            modes /= 10;
-            let mode2 = modes % 10;
-            modes /= 10;
-            let mode3 = modes % 10;
-
-            match op {
-                99 => break,
-                // TODO: Abstract over instructions, so that we can maybe inspect/modify programs too?
-                1 => {
-                    let a = self.param(self.ip + 1, mode1);
-                    let b = self.param(self.ip + 2, mode2);
-                    self.store(self.ip + 3, mode3, a + b);
-                    self.ip += 4;
-                }
-                2 => {
-                    let a = self.param(self.ip + 1, mode1);
-                    let b = self.param(self.ip + 2, mode2);
-                    self.store(self.ip + 3, mode3, a * b);
-                    self.ip += 4;
+            if let Some(result) = self.step() {
+                return result;
+            }
+        }
+    }
+
+    /// Runs a single instruction. Returns the result if the program outputs, needs input or
+    /// halted, otherwise `None`.
+    pub fn step(&mut self) -> Option<Result<W>> {
+        if self.taint.is_some() {
+            return taint::step(self);
+        }
+        let instruction = self.instruction();
+        if self.observers.is_some() {
+            self.notify_step(instruction);
+        }
+        if let Some(loops) = &mut self.loops {
+            loops.steps += 1;
+        }
+        let Instruction {
+            opcode: op,
+            modes: [mode1, mode2, mode3],
+        } = instruction;
+
+        match op {
+            99 => return Some(Result::Halt),
+            // TODO: Abstract over instructions, so that we can maybe inspect/modify programs too?
+            1 => {
+                let a = self.param(self.ip + 1, mode1);
+                let b = self.param(self.ip + 2, mode2);
+                match self.overflow.add(a, b) {
+                    Some(result) => self.store(self.ip + 3, mode3, result),
+                    None => return Some(Result::Error(Error::Overflow { ip: self.ip })),
                 }
-                3 => {
-                    // input
-                    if let Some(input) = self.inputs.pop_front() {
-                        self.store(self.ip + 1, mode1, input);
-                    } else {
-                        return Result::NeedInput;
-                    }
-                    self.ip += 2;
+                self.ip += 4;
+            }
+            2 => {
+                let a = self.param(self.ip + 1, mode1);
+                let b = self.param(self.ip + 2, mode2);
+                match self.overflow.mul(a, b) {
+                    Some(result) => self.store(self.ip + 3, mode3, result),
+                    None => return Some(Result::Error(Error::Overflow { ip: self.ip })),
                 }
-                4 => {
-                    // output
-                    let a = self.param(self.ip + 1, mode1);
-                    self.ip += 2;
-                    return Result::Output(a);
+                self.ip += 4;
+            }
+            3 => {
+                // input
+                if let Some(input) = self.next_input() {
+                    self.store(self.ip + 1, mode1, input);
+                } else {
+                    return Some(Result::NeedInput);
                 }
-                5 => {
-                    // jump-if-true
-                    let a = self.param(self.ip + 1, mode1);
-                    let b = self.param(self.ip + 2, mode2);
-                    if a != 0 {
-                        self.ip = b;
-                    } else {
-                        self.ip += 3;
+                self.ip += 2;
+            }
+            4 => {
+                // output
+                let a = self.param(self.ip + 1, mode1);
+                self.ip += 2;
+                self.notify_output(&a);
+                self.reset_loops();
+                return Some(Result::Output(a));
+            }
+            5 => {
+                // jump-if-true
+                let a = self.param(self.ip + 1, mode1);
+                let b = self.param(self.ip + 2, mode2);
+                if !a.is_zero() {
+                    let target = address(&b);
+                    if target <= self.ip && self.loops.is_some() {
+                        self.ip = target;
+                        return self.check_loop().map(Result::Looping);
                     }
+                    self.ip = target;
+                } else {
+                    self.ip += 3;
                 }
-                6 => {
-                    // jump-if-false
-                    let a = self.param(self.ip + 1, mode1);
-                    let b = self.param(self.ip + 2, mode2);
-                    if a == 0 {
-                        self.ip = b;
-                    } else {
-                        self.ip += 3;
+            }
+            6 => {
+                // jump-if-false
+                let a = self.param(self.ip + 1, mode1);
+                let b = self.param(self.ip + 2, mode2);
+                if a.is_zero() {
+                    let target = address(&b);
+                    if target <= self.ip && self.loops.is_some() {
+                        self.ip = target;
+                        return self.check_loop().map(Result::Looping);
                     }
+                    self.ip = target;
+                } else {
+                    self.ip += 3;
                 }
-                7 => {
-                    // less than
-                    let a = self.param(self.ip + 1, mode1);
-                    let b = self.param(self.ip + 2, mode2);
-                    if a < b {
-                        self.store(self.ip + 3, mode3, 1);
-                    } else {
-                        self.store(self.ip + 3, mode3, 0);
-                    }
-                    self.ip += 4;
+            }
+            7 => {
+                // less than
+                let a = self.param(self.ip + 1, mode1);
+                let b = self.param(self.ip + 2, mode2);
+                if a < b {
+                    self.store(self.ip + 3, mode3, W::from(1));
+                } else {
+                    self.store(self.ip + 3, mode3, W::zero());
                 }
-                8 => {
-                    // equals
-                    let a = self.param(self.ip + 1, mode1);
-                    let b = self.param(self.ip + 2, mode2);
-                    if a == b {
-                        self.store(self.ip + 3, mode3, 1);
-                    } else {
-                        self.store(self.ip + 3, mode3, 0);
-                    }
-                    self.ip += 4;
+                self.ip += 4;
+            }
+            8 => {
+                // equals
+                let a = self.param(self.ip + 1, mode1);
+                let b = self.param(self.ip + 2, mode2);
+                if a == b {
+                    self.store(self.ip + 3, mode3, W::from(1));
+                } else {
+                    self.store(self.ip + 3, mode3, W::zero());
                 }
-                9 => {
-                    // adjusts the relative base
-                    let a = self.param(self.ip + 1, mode1);
-                    self.relative_base += a;
-                    self.ip += 2;
+                self.ip += 4;
+            }
+            9 => {
+                // adjusts the relative base
+                let a = self.param(self.ip + 1, mode1);
+                self.relative_base += address(&a);
+                self.ip += 2;
+            }
+            _ => return self.run_registered(op, [mode1, mode2, mode3]),
+        }
+        None
+    }
+
+    fn run_registered(&mut self, op: i64, modes: [i64; 3]) -> Option<Result<W>> {
+        let opcode = match self.opcodes.get(&op) {
+            Some(opcode) => opcode.clone(),
+            None => unimplemented!("Unknown opcode {}", op),
+        };
+        let ip = self.ip;
+        let mut operands = Operands {
+            code: self,
+            modes,
+            params: opcode.params,
+            jumped: false,
+        };
+        let result = (opcode.handler)(&mut operands);
+        let jumped = operands.jumped;
+        match result {
+            None | Some(Result::Output(_)) if !jumped => self.ip = ip + opcode.params as i64 + 1,
+            _ => {}
+        }
+        if let Some(Result::Output(o)) = &result {
+            self.notify_output(o);
+            self.reset_loops();
+        }
+        result
+    }
+
+    /// Called after a backward jump, returns the cycle length if the state repeated.
+    fn check_loop(&mut self) -> Option<u64> {
+        let mut hasher = Fnv::default();
+        (self.ip, self.relative_base, &self.prog).hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let loops = self.loops.as_mut()?;
+        if let Some(candidate) = &loops.candidate {
+            if loops.steps == candidate.steps
+                && candidate.ip == self.ip
+                && candidate.relative_base == self.relative_base
+                && candidate.prog == self.prog
+            {
+                return Some(candidate.cycle);
+            }
+            if loops.steps >= candidate.steps {
+                loops.candidate = None;
+            }
+        }
+        if loops.candidate.is_none() {
+            if let Some(&previous) = loops.seen.get(&hash) {
+                let cycle = loops.steps - previous;
+                loops.candidate = Some(Candidate {
+                    steps: loops.steps + cycle,
+                    cycle,
+                    ip: self.ip,
+                    relative_base: self.relative_base,
+                    prog: self.prog.clone(),
+                });
+            }
+        }
+        loops.seen.insert(hash, loops.steps);
+        None
+    }
+
+    fn reset_loops(&mut self) {
+        if let Some(loops) = &mut self.loops {
+            loops.seen.clear();
+            loops.candidate = None;
+        }
+    }
+
+    // Cold so that the checks for observers are all that's left in the hot paths
+
+    #[cold]
+    fn notify_step(&self, instruction: Instruction) {
+        if let Some(observers) = &self.observers {
+            for f in &observers.step {
+                f(self.ip, instruction);
+            }
+        }
+    }
+
+    #[cold]
+    fn notify_write(&self, address: i64, value: &W) {
+        if let Some(observers) = &self.observers {
+            for f in &observers.write {
+                f(address, value);
+            }
+        }
+    }
+
+    #[cold]
+    fn notify_output(&self, output: &W) {
+        if let Some(observers) = &self.observers {
+            for f in &observers.output {
+                f(output);
+            }
+        }
+    }
+
+    pub fn ip(&self) -> i64 {
+        self.ip
+    }
+
+    pub fn set_ip(&mut self, ip: i64) {
+        self.ip = ip;
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn set_relative_base(&mut self, relative_base: i64) {
+        self.relative_base = relative_base;
+    }
+
+    /// Takes the next input that was added, e.g. for running instructions outside of `run`.
+    pub fn next_input(&mut self) -> Option<W> {
+        let input = self.inputs.pop_front();
+        if input.is_some() {
+            self.reset_loops();
+        }
+        if let (Some(observers), Some(input)) = (&self.observers, &input) {
+            for f in &observers.input {
+                f(input);
+            }
+        }
+        input
+    }
+
+    fn instruction(&mut self) -> Instruction {
+        let ip = self.ip as usize;
+        if let Some(decoded) = &mut self.decoded {
+            match decoded.get(ip) {
+                Some(Some(instruction)) => return *instruction,
+                Some(None) => {
+                    if let Some(instruction) = cache_entry(&self.prog[ip]) {
+                        Arc::make_mut(decoded)[ip] = Some(instruction);
+                        return instruction;
+                    }
                 }
-                _ => unimplemented!("Unknown opcode {}", op),
+                None => {}
             }
         }
-        Result::Halt
+        decode(&self.get(self.ip))
     }
 
-    fn get(&mut self, p: i64) -> i64 {
+    fn get(&mut self, p: i64) -> W {
+        if let Some(value) = self.prog.get(p as usize) {
+            return value.clone();
+        }
         self.ensure_memory(p);
-        self.prog[p as usize]
+        self.prog[p as usize].clone()
     }
 
-    fn set(&mut self, p: i64, value: i64) {
+    fn set(&mut self, p: i64, value: W) {
+        if self.observers.is_some() {
+            self.notify_write(p, &value);
+        }
+        if let Some(decoded) = &mut self.decoded {
+            if let Some(Some(_)) = decoded.get(p as usize) {
+                Arc::make_mut(decoded)[p as usize] = None;
+            }
+        }
         self.ensure_memory(p);
         self.prog[p as usize] = value;
     }
@@ -174,25 +834,25 @@ impl Intcode {
     fn ensure_memory(&mut self, p: i64) {
         let needed_size = p as usize + 1;
         if needed_size > self.prog.len() {
-            self.prog.resize(needed_size, 0);
+            self.prog.resize(needed_size, W::zero());
         }
     }
 
-    fn param(&mut self, p: i64, mode: i64) -> i64 {
+    fn param(&mut self, p: i64, mode: i64) -> W {
         let val = self.get(p);
         match mode {
             // position
-            0 => self.get(val),
+            0 => self.get(address(&val)),
             // immediate
             1 => val,
             // relative
-            2 => self.get(self.relative_base + val),
+            2 => self.get(self.relative_base + address(&val)),
             _ => panic!("Unknown mode {} for param", mode),
         }
     }
 
-    fn store(&mut self, p: i64, mode: i64, result: i64) {
-        let val = self.get(p);
+    fn store(&mut self, p: i64, mode: i64, result: W) {
+        let val = address(&self.get(p));
         match mode {
             0 => self.set(val, result),
             2 => self.set(self.relative_base + val, result),
@@ -201,6 +861,77 @@ impl Intcode {
     }
 }
 
+/// Iterator returned by [`Intcode::outputs`].
+pub struct Outputs<'a, W> {
+    code: &'a mut Intcode<W>,
+}
+
+impl<'a, W: Word> Iterator for Outputs<'a, W> {
+    type Item = W;
+
+    fn next(&mut self) -> Option<W> {
+        match self.code.run() {
+            Result::Output(o) => Some(o),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator returned by [`Intcode::feed`].
+pub struct Feed<'a, W, I> {
+    code: &'a mut Intcode<W>,
+    inputs: I,
+}
+
+impl<'a, W: Word, I: Iterator<Item = W>> Iterator for Feed<'a, W, I> {
+    type Item = W;
+
+    fn next(&mut self) -> Option<W> {
+        loop {
+            match self.code.run() {
+                Result::Output(o) => return Some(o),
+                Result::NeedInput => {
+                    let input = self.inputs.next()?;
+                    self.code.add_input(input);
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`Intcode::chunks`].
+pub struct Chunks<'a, W> {
+    code: &'a mut Intcode<W>,
+    n: usize,
+}
+
+impl<'a, W: Word> Iterator for Chunks<'a, W> {
+    type Item = Vec<W>;
+
+    fn next(&mut self) -> Option<Vec<W>> {
+        let chunk: Vec<W> = self.code.outputs().take(self.n).collect();
+        if chunk.len() == self.n {
+            Some(chunk)
+        } else {
+            None
+        }
+    }
+}
+
+/// Entry of [`Intcode::enable_cache`] for a cell, `None` if it isn't a known instruction.
+fn cache_entry<W: Word>(value: &W) -> Option<Instruction> {
+    let instruction = Instruction::decode(value.to_i64()?);
+    instruction.params().map(|_| instruction)
+}
+
+fn decode<W: Word>(instruction: &W) -> Instruction {
+    let instruction = instruction
+        .to_i64()
+        .unwrap_or_else(|| panic!("Unknown instruction {}", instruction));
+    Instruction::decode(instruction)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,4 +977,228 @@ mod tests {
         assert_eq!(code.clone().add_input(1).run_all(), vec![2955820355]);
         assert_eq!(code.clone().add_input(2).run_all(), vec![46643]);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_big_int() {
+        let code = Intcode::<BigInt>::parse_words("1102,34915192,34915192,7,4,7,99,0");
+        assert_eq!(code.clone().run_last(), BigInt::from(1219070632396864i64));
+
+        // Doesn't fit in an i64 anymore
+        let code = Intcode::<BigInt>::parse_words("1102,9223372036854775807,4,7,4,7,99,0");
+        assert_eq!(
+            code.clone().run_last(),
+            "36893488147419103228".parse().unwrap()
+        );
+
+        let input = include_str!("../input/2019/day09.txt");
+        let mut code = Intcode::<BigInt>::parse_words(input);
+        code.enable_cache();
+        assert_eq!(
+            code.clone().add_input(BigInt::from(1)).run_all(),
+            vec![BigInt::from(2955820355i64)]
+        );
+        assert_eq!(
+            code.clone().add_input(BigInt::from(2)).run_all(),
+            vec![BigInt::from(46643)]
+        );
+    }
+
+    #[test]
+    fn test_register_opcode() {
+        use std::sync::atomic::{AtomicI64, Ordering};
+        use std::sync::Mutex;
+
+        // 42 stores a "random" number, 50 prints its parameter to a log
+        let mut code = Intcode::parse("42,13,50,13,1001,13,1,13,50,13,4,13,99");
+        let random = Arc::new(AtomicI64::new(4));
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let r = random.clone();
+        let l = log.clone();
+        code.register_opcode(42, 1, move |operands| {
+            operands.set(0, r.fetch_add(1, Ordering::SeqCst));
+            None
+        })
+        .register_opcode(50, 1, move |operands| {
+            let value = operands.get(0);
+            l.lock().unwrap().push(value);
+            None
+        });
+
+        assert_eq!(code.run_all(), vec![5]);
+        assert_eq!(*log.lock().unwrap(), vec![4, 5]);
+        assert_eq!(random.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_register_opcode_output_and_jump() {
+        // 30 outputs the sum of its parameters and skips the next instruction
+        let mut code = Intcode::parse("21130,3,4,104,7,104,8,99");
+        code.register_opcode(30, 2, |operands| {
+            let sum = operands.get(0) + operands.get(1);
+            let ip = operands.ip();
+            operands.jump(ip + 5);
+            Some(Result::Output(sum))
+        });
+        assert_eq!(code.run_all(), vec![7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Opcode 130 is not between 1 and 99")]
+    fn test_register_opcode_out_of_range() {
+        // Would be decoded as opcode 30 with mode 1
+        Intcode::parse("99").register_opcode(130, 0, |_| None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Parameter 2 is out of range, the opcode has 2 parameters")]
+    fn test_register_opcode_param_out_of_range() {
+        let mut code = Intcode::parse("1130,0,0,99");
+        code.register_opcode(30, 2, |operands| Some(Result::Output(operands.get(2))));
+        code.run();
+    }
+
+    #[test]
+    fn test_outputs() {
+        // Outputs 1 and 2, then needs input and outputs it
+        let mut code = Intcode::parse("104,1,104,2,3,9,4,9,99,0");
+        assert_eq!(code.outputs().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(code.outputs().next(), None);
+        code.add_input(3);
+        assert_eq!(code.outputs().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn test_feed() {
+        // Outputs each input times 2, until the input is 0
+        let code = Intcode::parse("3,15,1006,15,14,1002,15,2,16,4,16,1105,1,0,99,0,0");
+        let outputs: Vec<i64> = code.clone().feed(vec![1, 2, 3, 0, 4]).collect();
+        assert_eq!(outputs, vec![2, 4, 6]);
+
+        // Inputs are only taken when needed
+        let mut inputs = 1..;
+        let mut code = code.clone();
+        let outputs: Vec<i64> = code.feed(&mut inputs).take(2).collect();
+        assert_eq!(outputs, vec![2, 4]);
+        assert_eq!(inputs.next(), Some(3));
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut code = Intcode::parse("104,1,104,2,104,3,104,4,104,5,99");
+        assert_eq!(
+            code.chunks(2).collect::<Vec<_>>(),
+            vec![vec![1, 2], vec![3, 4]]
+        );
+    }
+
+    #[test]
+    fn test_observers() {
+        use std::sync::Mutex;
+
+        // Adds input to 10 and outputs it
+        let mut code = Intcode::parse("3,9,1001,9,10,9,4,9,99,0");
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let e = events.clone();
+        code.on_step(move |ip, instruction| {
+            e.lock()
+                .unwrap()
+                .push(format!("step {} {}", ip, instruction.opcode))
+        });
+        let e = events.clone();
+        code.on_write(move |address, value| {
+            e.lock()
+                .unwrap()
+                .push(format!("write {} {}", address, value))
+        });
+        let e = events.clone();
+        code.on_input(move |value| e.lock().unwrap().push(format!("input {}", value)));
+        let e = events.clone();
+        code.on_output(move |value| e.lock().unwrap().push(format!("output {}", value)));
+
+        assert_eq!(code.add_input(5).run_all(), vec![15]);
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "step 0 3",
+                "input 5",
+                "write 9 5",
+                "step 2 1",
+                "write 9 15",
+                "step 6 4",
+                "output 15",
+                "step 8 99",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_loops() {
+        // Counts up to 3, outputs 7, then jumps to itself forever
+        let mut code = Intcode::parse("1001,16,1,16,1007,16,3,17,1005,17,0,104,7,1105,1,13,0,0");
+        code.detect_loops();
+        assert_eq!(code.run(), Result::Output(7));
+        assert_eq!(code.run(), Result::Looping(1));
+
+        // A loop that changes memory every time isn't looping
+        let mut code = Intcode::parse("1001,7,1,7,1105,1,0,0");
+        code.detect_loops();
+        for _ in 0..1000 {
+            assert_eq!(code.step(), None);
+        }
+    }
+
+    #[test]
+    fn test_detect_loops_day9() {
+        let input = include_str!("../input/2019/day09.txt");
+        let mut code = Intcode::parse(input);
+        code.detect_loops();
+        assert_eq!(code.add_input(1).run_all(), vec![2955820355]);
+    }
+
+    #[test]
+    fn test_cache() {
+        let input = include_str!("../input/2019/day09.txt");
+        let mut code = Intcode::parse(input);
+        code.enable_cache();
+
+        assert_eq!(code.clone().add_input(1).run_all(), vec![2955820355]);
+        assert_eq!(code.clone().add_input(2).run_all(), vec![46643]);
+
+        // Day 5 adds the input to an opcode, so the entry needs to be invalidated
+        let input = include_str!("../input/2019/day05.txt");
+        let mut code = Intcode::parse(input);
+        code.enable_cache();
+        let mut first = code.clone();
+        assert_eq!(first.add_input(1).run_last(), 5346030);
+        assert_eq!(code.clone().add_input(5).run_last(), 513116);
+        // The entry is decoded again when the instruction runs, only in the clone that wrote it
+        let decoded = |code: &Intcode| code.decoded.as_ref().unwrap()[6];
+        assert_eq!(decoded(&first), Some(Instruction::decode(first.prog[6])));
+        assert_ne!(decoded(&code), decoded(&first));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let code = Intcode::parse("1102,9223372036854775807,2,7,4,7,99,0");
+        assert_eq!(code.clone().run_last(), -2);
+        assert_eq!(
+            code.clone().set_overflow(Overflow::Saturate).run_last(),
+            i64::MAX
+        );
+
+        let mut code = code.clone();
+        code.set_overflow(Overflow::Trap);
+        assert_eq!(code.run(), Result::Error(Error::Overflow { ip: 0 }));
+        // Stays at the faulting instruction
+        assert_eq!(code.ip(), 0);
+
+        let code = Intcode::parse("1101,-9223372036854775808,-1,7,4,7,99,0");
+        assert_eq!(code.clone().run_last(), i64::MAX);
+        assert_eq!(
+            code.clone().set_overflow(Overflow::Saturate).run_last(),
+            i64::MIN
+        );
+    }
 }
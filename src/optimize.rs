@@ -0,0 +1,543 @@
+//! Optimizer for Intcode programs.
+//!
+//! Instructions can't be removed or moved without changing the addresses that jumps go to, so
+//! the optimizer only rewrites instructions in place: parameters read from cells that are never
+//! written become immediates, arithmetic on immediates is folded, conditional jumps that are
+//! always taken become unconditional, and cells that are neither reachable code nor data are
+//! cleared.
+//!
+//! This is only safe if we know which cells the program reads and writes, so the optimizer
+//! leaves the program alone if it uses relative mode, jumps to computed addresses or writes to
+//! its own instructions in a way that changes control flow.
+
+use crate::Instruction;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, Default)]
+pub struct Analysis {
+    /// Start of every instruction that can be reached, assuming every jump can be taken
+    pub instructions: BTreeSet<usize>,
+    /// Cells that reachable instructions write to
+    pub written: BTreeSet<usize>,
+    /// Cells that reachable instructions read as data
+    pub read: BTreeSet<usize>,
+    /// Start of reachable instructions that have cells written to by the program, including ones
+    /// that are only valid after being written to
+    pub self_modified: BTreeSet<usize>,
+    /// Whether the cells above could be determined
+    pub complete: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// Parameters that were turned into immediates
+    pub propagated: usize,
+    /// Instructions that were folded into storing a constant
+    pub folded: usize,
+    /// Conditional jumps that were turned into unconditional ones
+    pub jumps: usize,
+    /// Cells that were cleared because they are unreachable and not used as data
+    pub removed: usize,
+}
+
+/// Returns `(instruction, params)` if the instruction at `ip` is valid and fits in `prog`.
+fn fetch(prog: &[i64], ip: usize) -> Option<(Instruction, usize)> {
+    let instruction = Instruction::decode(*prog.get(ip)?);
+    let params = instruction.params()?;
+    if ip + params >= prog.len() {
+        return None;
+    }
+    for i in 0..params {
+        let mode = instruction.modes[i];
+        let store = writes_param(instruction.opcode) == Some(i);
+        if mode > 2 || (store && mode == 1) {
+            return None;
+        }
+    }
+    Some((instruction, params))
+}
+
+/// Index of the parameter that is written to, if any.
+fn writes_param(opcode: i64) -> Option<usize> {
+    match opcode {
+        1 | 2 | 7 | 8 => Some(2),
+        3 => Some(0),
+        _ => None,
+    }
+}
+
+pub fn analyze(prog: &[i64]) -> Analysis {
+    let mut analysis = Analysis {
+        complete: true,
+        ..Analysis::default()
+    };
+    // Cells whose values are used as addresses or jump targets
+    let mut addresses = BTreeSet::new();
+
+    let mut fetched = BTreeSet::new();
+    let mut pending = vec![0];
+    while let Some(ip) = pending.pop() {
+        if !fetched.insert(ip) {
+            continue;
+        }
+        addresses.insert(ip);
+        // An invalid instruction stops the program, so nothing to follow from there
+        let (instruction, params) = match fetch(prog, ip) {
+            Some(fetched) => fetched,
+            None => continue,
+        };
+        analysis.instructions.insert(ip);
+
+        for i in 0..params {
+            let cell = ip + 1 + i;
+            match instruction.modes[i] {
+                0 => {
+                    addresses.insert(cell);
+                    if prog[cell] < 0 {
+                        analysis.complete = false;
+                    } else if writes_param(instruction.opcode) == Some(i) {
+                        analysis.written.insert(prog[cell] as usize);
+                    } else {
+                        analysis.read.insert(prog[cell] as usize);
+                    }
+                }
+                2 => analysis.complete = false,
+                _ => {}
+            }
+        }
+
+        match instruction.opcode {
+            99 => {}
+            5 | 6 => {
+                // Where the jump goes to needs to be known, so neither its parameters nor the
+                // cell they point to can be written
+                addresses.extend(&[ip + 1, ip + 2]);
+                let target = match instruction.modes[1] {
+                    1 => prog[ip + 2],
+                    _ => prog.get(prog[ip + 2] as usize).cloned().unwrap_or(0),
+                };
+                if instruction.modes[1] == 0 {
+                    addresses.insert(prog[ip + 2] as usize);
+                }
+                if target < 0 {
+                    analysis.complete = false;
+                } else {
+                    pending.push(target as usize);
+                }
+                pending.push(ip + 3);
+            }
+            _ => pending.push(ip + params + 1),
+        }
+    }
+
+    for ip in fetched {
+        let params = fetch(prog, ip).map(|(_, params)| params).unwrap_or(0);
+        if (ip..=ip + params).any(|cell| analysis.written.contains(&cell)) {
+            analysis.self_modified.insert(ip);
+        }
+    }
+    if addresses.iter().any(|cell| analysis.written.contains(cell)) {
+        analysis.complete = false;
+    }
+    analysis
+}
+
+/// Where the branches of each conditional jump that can be reached from `entry` join again: the
+/// jump's immediate post-dominator, the first instruction that every path from it runs before the
+/// program halts or the function returns. Jumps without one aren't included. Also returns the
+/// cells the graph depends on (the first cell of each instruction and the parameters of jumps),
+/// as the result only holds while the program doesn't write to them.
+///
+/// Unlike [`analyze`], a jump that calls a function is assumed to return to the instruction after
+/// it, and the function is analyzed on its own. A call is recognized by the instruction before the
+/// jump storing the address after it, possibly followed by adjusting the relative base (see
+/// [`crate::callstack`]). Jumps to addresses that aren't immediates are assumed to return.
+pub(crate) fn joins(prog: &[i64], entry: usize) -> (BTreeMap<usize, usize>, BTreeSet<usize>) {
+    const EXIT: usize = usize::MAX;
+    let mut successors: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    let mut jumps = Vec::new();
+    let mut cells = BTreeSet::new();
+    let mut pending = vec![entry];
+    while let Some(ip) = pending.pop() {
+        if ip == EXIT || successors.contains_key(&ip) {
+            continue;
+        }
+        let next = match fetch(prog, ip) {
+            Some((instruction, params)) => {
+                cells.insert(ip);
+                match instruction.opcode {
+                    99 => vec![EXIT],
+                    5 | 6 => {
+                        jumps.push(ip);
+                        cells.extend(&[ip + 1, ip + 2]);
+                        let target = match (instruction.modes[1], prog[ip + 2]) {
+                            (1, target) if target >= 0 => target as usize,
+                            _ => EXIT,
+                        };
+                        let taken = match instruction.modes[0] {
+                            1 => Some((prog[ip + 1] != 0) == (instruction.opcode == 5)),
+                            _ => None,
+                        };
+                        match taken {
+                            Some(true) if target != EXIT && is_call(prog, ip) => {
+                                pending.push(target);
+                                vec![ip + 3]
+                            }
+                            Some(true) => vec![target],
+                            Some(false) => vec![ip + 3],
+                            None => vec![target, ip + 3],
+                        }
+                    }
+                    _ => vec![ip + params + 1],
+                }
+            }
+            // An invalid instruction stops the program
+            None => vec![EXIT],
+        };
+        pending.extend(&next);
+        successors.insert(ip, next);
+    }
+
+    // Post-dominators are the dominators of the reversed graph, see "A Simple, Fast Dominance
+    // Algorithm" by Cooper, Harvey and Kennedy. Instructions that can't reach the exit (because
+    // they loop forever) have none.
+    let mut predecessors: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (&ip, next) in &successors {
+        for &n in next {
+            predecessors.entry(n).or_default().push(ip);
+        }
+    }
+    let mut postorder = Vec::new();
+    let mut order = BTreeMap::new();
+    let mut visited = BTreeSet::new();
+    visited.insert(EXIT);
+    let mut stack = vec![(EXIT, 0)];
+    while let Some((node, i)) = stack.pop() {
+        match predecessors.get(&node).and_then(|p| p.get(i)) {
+            Some(&p) => {
+                stack.push((node, i + 1));
+                if visited.insert(p) {
+                    stack.push((p, 0));
+                }
+            }
+            None => {
+                order.insert(node, postorder.len());
+                postorder.push(node);
+            }
+        }
+    }
+    let mut ipdom = BTreeMap::new();
+    ipdom.insert(EXIT, EXIT);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        // The exit is last in postorder
+        for &node in postorder.iter().rev().skip(1) {
+            let mut new = None;
+            for &s in &successors[&node] {
+                if ipdom.contains_key(&s) {
+                    new = Some(match new {
+                        None => s,
+                        Some(mut a) => {
+                            let mut b = s;
+                            while a != b {
+                                while order[&a] < order[&b] {
+                                    a = ipdom[&a];
+                                }
+                                while order[&b] < order[&a] {
+                                    b = ipdom[&b];
+                                }
+                            }
+                            a
+                        }
+                    });
+                }
+            }
+            if let Some(new) = new {
+                if ipdom.insert(node, new) != Some(new) {
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let joins = jumps
+        .into_iter()
+        .filter_map(|ip| match ipdom.get(&ip) {
+            Some(&join) if join != EXIT => Some((ip, join)),
+            _ => None,
+        })
+        .collect();
+    (joins, cells)
+}
+
+/// Whether the jump at `ip` calls a function: the instruction before it stores the address after
+/// the jump, or the one before that if it adjusts the relative base.
+fn is_call(prog: &[i64], ip: usize) -> bool {
+    let stores_next = |at: usize| match fetch(prog, at) {
+        Some((instruction, 3)) if instruction.opcode == 1 || instruction.opcode == 2 => {
+            (0..2).any(|i| instruction.modes[i] == 1 && prog[at + 1 + i] == ip as i64 + 3)
+        }
+        _ => false,
+    };
+    let adjusts = |at: usize| matches!(fetch(prog, at), Some((Instruction { opcode: 9, .. }, 1)));
+    (ip >= 4 && stores_next(ip - 4)) || (ip >= 6 && adjusts(ip - 2) && stores_next(ip - 6))
+}
+
+pub fn optimize(prog: &[i64]) -> (Vec<i64>, Stats) {
+    let analysis = analyze(prog);
+    let mut stats = Stats::default();
+    if !analysis.complete {
+        return (prog.to_vec(), stats);
+    }
+
+    // Cells that aren't written keep their initial value
+    let constant = |cell: usize, mode: i64| -> Option<i64> {
+        if analysis.written.contains(&cell) {
+            return None;
+        }
+        match mode {
+            1 => Some(prog[cell]),
+            _ => {
+                let address = prog[cell] as usize;
+                if analysis.written.contains(&address) {
+                    None
+                } else {
+                    Some(prog.get(address).cloned().unwrap_or(0))
+                }
+            }
+        }
+    };
+
+    let mut result = prog.to_vec();
+    let mut reachable = BTreeSet::new();
+    let mut pending = vec![0];
+    while let Some(ip) = pending.pop() {
+        if !reachable.insert(ip) {
+            continue;
+        }
+        let (instruction, params) = match fetch(prog, ip) {
+            Some(fetched) => fetched,
+            None => continue,
+        };
+        let cells = ip..=ip + params;
+        let frozen = cells
+            .clone()
+            .any(|c| analysis.written.contains(&c) || analysis.read.contains(&c));
+        let [mode1, mode2, mode3] = instruction.modes;
+        let mut rewritten = prog[cells.clone()].to_vec();
+
+        match instruction.opcode {
+            99 => continue,
+            1 | 2 | 7 | 8 => {
+                let a = constant(ip + 1, mode1);
+                let b = constant(ip + 2, mode2);
+                let folded = match (instruction.opcode, a, b) {
+                    (1, Some(a), Some(b)) => a.checked_add(b),
+                    (2, Some(a), Some(b)) => a.checked_mul(b),
+                    (7, Some(a), Some(b)) => Some((a < b) as i64),
+                    (8, Some(a), Some(b)) => Some((a == b) as i64),
+                    _ => None,
+                };
+                if let Some(value) = folded {
+                    rewritten = vec![1101 + mode3 * 10000, value, 0, prog[ip + 3]];
+                } else {
+                    propagate(&mut rewritten, &[a, b]);
+                }
+                pending.push(ip + 4);
+            }
+            4 => {
+                propagate(&mut rewritten, &[constant(ip + 1, mode1)]);
+                pending.push(ip + 2);
+            }
+            5 | 6 => {
+                let target = match constant(ip + 2, mode2) {
+                    Some(target) => target,
+                    None => return (prog.to_vec(), Stats::default()),
+                };
+                match constant(ip + 1, mode1) {
+                    Some(condition) if (condition != 0) == (instruction.opcode == 5) => {
+                        rewritten = vec![1105, 1, target];
+                        pending.push(target as usize);
+                    }
+                    Some(_) => pending.push(ip + 3),
+                    condition => {
+                        propagate(&mut rewritten, &[condition, Some(target)]);
+                        pending.push(target as usize);
+                        pending.push(ip + 3);
+                    }
+                }
+            }
+            _ => pending.push(ip + params + 1),
+        }
+
+        if !frozen && rewritten[..] != prog[cells.clone()] {
+            let was = Instruction::decode(prog[ip]);
+            let is = Instruction::decode(rewritten[0]);
+            if instruction.opcode == 5 || instruction.opcode == 6 {
+                if is.modes[0] == 1 && rewritten[1] == 1 {
+                    stats.jumps += 1;
+                } else {
+                    stats.propagated += 1;
+                }
+            } else if is.opcode != was.opcode || rewritten[2] == 0 && is.modes[1] == 1 {
+                stats.folded += 1;
+            } else {
+                stats.propagated += (0..params).filter(|&i| was.modes[i] != is.modes[i]).count();
+            }
+            result[cells].copy_from_slice(&rewritten);
+        }
+    }
+
+    let instruction_cells: BTreeSet<usize> = reachable
+        .iter()
+        .filter_map(|&ip| fetch(prog, ip).map(|(_, params)| ip..=ip + params))
+        .flatten()
+        .collect();
+    for (cell, value) in result.iter_mut().enumerate() {
+        if *value != 0
+            && !instruction_cells.contains(&cell)
+            && !analysis.read.contains(&cell)
+            && !analysis.written.contains(&cell)
+        {
+            *value = 0;
+            stats.removed += 1;
+        }
+    }
+
+    (result, stats)
+}
+
+/// Turns position mode parameters with a known value into immediates.
+fn propagate(instruction: &mut [i64], values: &[Option<i64>]) {
+    for (i, value) in values.iter().enumerate() {
+        let mode = 10i64.pow(i as u32 + 2);
+        if let Some(value) = value {
+            if instruction[0] / mode % 10 == 0 {
+                instruction[0] += mode;
+                instruction[i + 1] = *value;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Intcode;
+
+    #[test]
+    fn test_day5_input() {
+        let input = include_str!("../input/2019/day05.txt");
+        let prog = Intcode::parse(input).prog;
+        // Adds the input to the opcode at 6, so what it does depends on the input
+        let analysis = analyze(&prog);
+        assert!(analysis.self_modified.contains(&6));
+        assert!(!analysis.complete);
+
+        let (optimized, stats) = optimize(&prog);
+        assert_eq!(optimized, prog);
+        assert_eq!(stats, Stats::default());
+
+        let code = Intcode::new(optimized);
+        assert_eq!(code.clone().add_input(1).run_last(), 5346030);
+        assert_eq!(code.clone().add_input(5).run_last(), 513116);
+    }
+
+    #[test]
+    fn test_day5_examples() {
+        let code = Intcode::parse(
+            "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99");
+        let (optimized, stats) = optimize(&code.prog);
+        // The always taken jump at the end is turned into the canonical 1105,1
+        assert_eq!(stats.jumps, 1);
+
+        let code = Intcode::new(optimized);
+        assert_eq!(code.clone().add_input(7).run(), crate::Result::Output(999));
+        assert_eq!(code.clone().add_input(8).run(), crate::Result::Output(1000));
+        assert_eq!(code.clone().add_input(9).run(), crate::Result::Output(1001));
+    }
+
+    #[test]
+    fn test_day9_examples() {
+        let code = Intcode::parse("1102,34915192,34915192,7,4,7,99,0");
+        let (optimized, stats) = optimize(&code.prog);
+        assert_eq!(optimized, vec![1101, 1219070632396864, 0, 7, 4, 7, 99, 0]);
+        assert_eq!(stats.folded, 1);
+        assert_eq!(Intcode::new(optimized).run_last(), 1219070632396864);
+
+        // Uses relative mode, so we can't tell what it reads or writes
+        let quine = Intcode::parse("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+        assert!(!analyze(&quine.prog).complete);
+        assert_eq!(optimize(&quine.prog).0, quine.prog);
+    }
+
+    #[test]
+    fn test_day9_input() {
+        let input = include_str!("../input/2019/day09.txt");
+        let (optimized, _) = optimize(&Intcode::parse(input).prog);
+        let code = Intcode::new(optimized);
+        assert_eq!(code.clone().add_input(1).run_all(), vec![2955820355]);
+        assert_eq!(code.clone().add_input(2).run_all(), vec![46643]);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        // Jumps over the output of 5, which is then cleared
+        let prog = vec![1105, 1, 5, 104, 5, 104, 6, 99];
+        let analysis = analyze(&prog);
+        assert!(analysis.complete);
+        assert!(analysis.self_modified.is_empty());
+
+        let (optimized, stats) = optimize(&prog);
+        assert_eq!(optimized, vec![1105, 1, 5, 0, 0, 104, 6, 99]);
+        assert_eq!(stats.removed, 2);
+    }
+
+    #[test]
+    fn test_self_modified() {
+        // Overwrites the immediate that is output with 7
+        let prog = vec![1101, 3, 4, 5, 104, 0, 99];
+        let analysis = analyze(&prog);
+        assert!(analysis.complete);
+        assert_eq!(analysis.written.iter().collect::<Vec<_>>(), vec![&5]);
+        assert!(analysis.self_modified.contains(&4));
+
+        let (optimized, _) = optimize(&prog);
+        assert_eq!(Intcode::new(optimized).run_all(), vec![7]);
+    }
+
+    #[test]
+    fn test_joins() {
+        // Reads a number, outputs 1 if it's 0 and 2 otherwise, then 3
+        let prog = vec![
+            3, 100, 1005, 100, 10, 104, 1, 1105, 1, 12, 104, 2, 104, 3, 99,
+        ];
+        let (joins, cells) = joins(&prog, 0);
+        assert_eq!(
+            joins.into_iter().collect::<Vec<_>>(),
+            vec![(2, 12), (7, 12)]
+        );
+        // Opcodes and the parameters of jumps, but not the address the input is stored at
+        assert_eq!(
+            cells.into_iter().collect::<Vec<_>>(),
+            vec![0, 2, 3, 4, 5, 7, 8, 9, 10, 12, 14]
+        );
+    }
+
+    #[test]
+    fn test_self_modified_jump() {
+        // Changes the target of the jump from 7 to 9, which outputs 5
+        let prog = vec![1101, 0, 9, 6, 1105, 1, 7, 99, 0, 104, 5, 99];
+        let analysis = analyze(&prog);
+        assert!(!analysis.complete);
+        assert!(analysis.self_modified.contains(&4));
+
+        let (optimized, stats) = optimize(&prog);
+        assert_eq!(optimized, prog);
+        assert_eq!(stats, Stats::default());
+        assert_eq!(Intcode::new(optimized).run_all(), vec![5]);
+    }
+}
@@ -0,0 +1,160 @@
+//! Memoizing programs that are pure functions of their inputs, like the drone in day 19.
+//!
+//! An [`Oracle`] runs a fresh clone of the program for each new list of inputs, and remembers
+//! the outputs. The outputs can also be stored on disk, in a file named after a hash of the
+//! program, so that they survive between runs. Each line of the file has the inputs and outputs
+//! of one query, e.g. `3,4:1`.
+
+use crate::{Intcode, Word};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::hash::Hash;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+type Decoder<T, W> = dyn Fn(&[W]) -> T;
+
+pub struct Oracle<T, W = i64> {
+    code: Intcode<W>,
+    decode: Box<Decoder<T, W>>,
+    cache: HashMap<Vec<W>, Vec<W>>,
+    file: Option<File>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<T, W: Word + Hash> Oracle<T, W> {
+    /// An oracle that decodes the outputs of the program with `decode`.
+    pub fn new(code: Intcode<W>, decode: impl Fn(&[W]) -> T + 'static) -> Self {
+        Oracle {
+            code,
+            decode: Box::new(decode),
+            cache: HashMap::new(),
+            file: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Loads earlier results for the program from `dir` and stores new ones there.
+    pub fn cache_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<&mut Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        let path = self.cache_path(dir.as_ref());
+        if path.exists() {
+            for line in fs::read_to_string(&path)?.lines() {
+                let mut parts = line.splitn(2, ':');
+                let parse = |s: Option<&str>| -> Option<Vec<W>> {
+                    match s? {
+                        "" => Some(Vec::new()),
+                        s => s.split(',').map(|v| v.parse().ok()).collect(),
+                    }
+                };
+                match (parse(parts.next()), parse(parts.next())) {
+                    (Some(inputs), Some(outputs)) => {
+                        self.cache.insert(inputs, outputs);
+                    }
+                    _ => {
+                        let message = format!("Invalid line in {:?}: {:?}", path, line);
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+                    }
+                }
+            }
+        }
+        self.file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+        Ok(self)
+    }
+
+    /// The cache file for the program, named after an FNV-1a hash of it (stable between runs,
+    /// unlike `DefaultHasher`).
+    fn cache_path(&self, dir: &Path) -> PathBuf {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for value in &self.code.prog {
+            for byte in value.to_string().bytes().chain(Some(b',')) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        dir.join(format!("{:016x}.txt", hash))
+    }
+
+    pub fn query(&mut self, inputs: &[W]) -> T {
+        if let Some(outputs) = self.cache.get(inputs) {
+            self.hits += 1;
+            return (self.decode)(outputs);
+        }
+        self.misses += 1;
+
+        let mut code = self.code.clone();
+        for input in inputs {
+            code.add_input(input.clone());
+        }
+        let outputs = code.run_all();
+        if let Some(file) = &mut self.file {
+            writeln!(file, "{}:{}", join(inputs), join(&outputs))
+                .unwrap_or_else(|e| panic!("Error writing cache: {}", e));
+        }
+        let result = (self.decode)(&outputs);
+        self.cache.insert(inputs.to_vec(), outputs);
+        result
+    }
+
+    /// Queries answered from the cache, including ones loaded from disk.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Queries that ran the program.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+fn join<W: Word>(values: &[W]) -> String {
+    let strings: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    strings.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn drone() -> Oracle<bool> {
+        let code = Intcode::parse(include_str!("../input/2019/day19.txt"));
+        Oracle::new(code, |outputs| outputs == [1])
+    }
+
+    #[test]
+    fn test_query() {
+        let mut oracle = drone();
+        assert!(oracle.query(&[0, 0]));
+        assert!(!oracle.query(&[1, 0]));
+        assert!(oracle.query(&[0, 0]));
+        assert_eq!((oracle.hits(), oracle.misses()), (1, 2));
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        let dir = env::temp_dir().join(format!("intcode-oracle-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut oracle = drone();
+        oracle.cache_dir(&dir).unwrap();
+        let count = (0..10)
+            .flat_map(|y| (0..10).map(move |x| [x, y]))
+            .filter(|input| oracle.query(input))
+            .count();
+        assert_eq!((oracle.hits(), oracle.misses()), (0, 100));
+
+        let mut oracle = drone();
+        oracle.cache_dir(&dir).unwrap();
+        let cached = (0..10)
+            .flat_map(|y| (0..10).map(move |x| [x, y]))
+            .filter(|input| oracle.query(input))
+            .count();
+        assert_eq!(cached, count);
+        assert_eq!((oracle.hits(), oracle.misses()), (100, 0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
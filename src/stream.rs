@@ -0,0 +1,201 @@
+//! An async interface for Intcode machines: outputs are a [`Stream`] and inputs a [`Sink`].
+//!
+//! Instead of returning `NeedInput`, polling the stream suspends until an input is sent, so that
+//! a controller can be written as async code that sends a command and awaits the response. The
+//! stream ends when the machine halts, fails or loops, see [`AsyncIntcode::result`] for which:
+//!
+//! ```
+//! # use advent_of_code_2019::{stream::AsyncIntcode, Intcode, Result};
+//! use futures::{executor, SinkExt, StreamExt};
+//!
+//! // Outputs the input times 2
+//! let mut code = AsyncIntcode::new(Intcode::parse("3,9,1002,9,2,9,4,9,99,0"));
+//! executor::block_on(async {
+//!     code.send(21).await.unwrap();
+//!     assert_eq!(code.next().await, Some(42));
+//!     assert_eq!(code.next().await, None);
+//! });
+//! assert_eq!(code.result(), Some(&Result::Halt));
+//! ```
+
+use crate::{Intcode, Result, Word};
+use futures::{Sink, Stream};
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+pub struct AsyncIntcode<W = i64> {
+    pub code: Intcode<W>,
+    /// Set when the stream is waiting for input
+    waker: Option<Waker>,
+    result: Option<Result<W>>,
+}
+
+impl<W: Word> AsyncIntcode<W> {
+    pub fn new(code: Intcode<W>) -> Self {
+        AsyncIntcode {
+            code,
+            waker: None,
+            result: None,
+        }
+    }
+
+    /// Why the stream ended: [`Result::Halt`], [`Result::Error`] or [`Result::Looping`]. `None`
+    /// while it hasn't ended.
+    pub fn result(&self) -> Option<&Result<W>> {
+        self.result.as_ref()
+    }
+}
+
+impl<W: Word + Unpin> Stream for AsyncIntcode<W> {
+    type Item = W;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<W>> {
+        let this = self.get_mut();
+        if this.result.is_some() {
+            return Poll::Ready(None);
+        }
+        match this.code.run() {
+            Result::Output(o) => Poll::Ready(Some(o)),
+            Result::NeedInput => {
+                this.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            result => {
+                this.result = Some(result);
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl<W: Word + Unpin> Sink<W> for AsyncIntcode<W> {
+    type Error = Infallible;
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        _: &mut Context,
+    ) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, input: W) -> std::result::Result<(), Self::Error> {
+        let this = self.get_mut();
+        this.code.add_input(input);
+        if let Some(waker) = this.waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context,
+    ) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _: &mut Context,
+    ) -> Poll<std::result::Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Overflow};
+    use futures::{executor, future, SinkExt, StreamExt};
+    use std::collections::HashSet;
+
+    fn step((x, y): (i64, i64), direction: i64) -> (i64, i64) {
+        match direction {
+            1 => (x, y - 1),
+            2 => (x, y + 1),
+            3 => (x - 1, y),
+            _ => (x + 1, y),
+        }
+    }
+
+    /// Explores the day 15 maze depth-first, returns the distance to the oxygen system. The maze
+    /// has no loops, so the first path found is the shortest.
+    async fn find_oxygen(droid: &mut AsyncIntcode) -> usize {
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        let mut position = (0, 0);
+        seen.insert(position);
+        loop {
+            let direction = (1..=4).find(|&d| !seen.contains(&step(position, d)));
+            if let Some(direction) = direction {
+                let next = step(position, direction);
+                seen.insert(next);
+                droid.send(direction).await.unwrap();
+                match droid.next().await.unwrap() {
+                    0 => {}
+                    status => {
+                        position = next;
+                        path.push(direction);
+                        if status == 2 {
+                            return path.len();
+                        }
+                    }
+                }
+            } else {
+                let back = match path.pop().expect("No oxygen system found") {
+                    1 => 2,
+                    2 => 1,
+                    3 => 4,
+                    _ => 3,
+                };
+                droid.send(back).await.unwrap();
+                droid.next().await.unwrap();
+                position = step(position, back);
+            }
+        }
+    }
+
+    #[test]
+    fn test_day15() {
+        let code = Intcode::parse(include_str!("../input/2019/day15.txt"));
+        let mut droid = AsyncIntcode::new(code);
+        assert_eq!(executor::block_on(find_oxygen(&mut droid)), 374);
+    }
+
+    #[test]
+    fn test_split() {
+        // Outputs each input times 2, until the input is 0
+        let code = Intcode::parse("3,15,1006,15,14,1002,15,2,16,4,16,1105,1,0,99,0,0");
+        let (mut inputs, outputs) = AsyncIntcode::new(code).split();
+        let send = async {
+            for input in &[1, 2, 3, 0] {
+                inputs.send(*input).await.unwrap();
+            }
+        };
+        let (_, outputs) = executor::block_on(future::join(send, outputs.collect::<Vec<_>>()));
+        assert_eq!(outputs, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_error_and_looping() {
+        // Outputs 1, then overflows
+        let mut code = Intcode::parse("104,1,1102,4611686018427387904,4,0,99");
+        code.set_overflow(Overflow::Trap);
+        let mut code = AsyncIntcode::new(code);
+        let outputs = executor::block_on(code.by_ref().collect::<Vec<_>>());
+        assert_eq!(outputs, vec![1]);
+        assert_eq!(
+            code.result(),
+            Some(&Result::Error(Error::Overflow { ip: 2 }))
+        );
+        assert_eq!(executor::block_on(code.next()), None);
+
+        // Jumps to itself
+        let mut code = Intcode::parse("1105,1,0");
+        code.detect_loops();
+        let mut code = AsyncIntcode::new(code);
+        assert_eq!(executor::block_on(code.next()), None);
+        assert!(matches!(code.result(), Some(Result::Looping(_))));
+    }
+}
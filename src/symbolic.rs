@@ -0,0 +1,670 @@
+//! Symbolic execution of Intcode programs.
+//!
+//! Memory cells and inputs can be marked as unknowns. Running the program then produces one
+//! [`Path`] per branch taken on an unknown value, with the constraints for taking it and the
+//! outputs and memory as expressions over the unknowns. [`Path::solve`] can then find concrete
+//! values for the unknowns, e.g. to find the noun and verb for day 2 without trying all of them.
+
+use crate::{Instruction, Intcode};
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expr {
+    Const(i64),
+    /// Unknown with the given index, see [`Symbolic::unknown_cell`] and [`Symbolic::unknown_input`]
+    Var(usize),
+    Add(Rc<Expr>, Rc<Expr>),
+    Mul(Rc<Expr>, Rc<Expr>),
+    LessThan(Rc<Expr>, Rc<Expr>),
+    Equals(Rc<Expr>, Rc<Expr>),
+    /// Read from an unknown address, in the memory as it was at the time of the read
+    Load(Rc<Expr>, Rc<Vec<Expr>>),
+}
+
+/// An expression of the form `constant + sum(coefficient * var)`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Linear {
+    pub constant: i64,
+    pub coefficients: BTreeMap<usize, i64>,
+}
+
+impl Expr {
+    pub fn sum(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a.wrapping_add(b)),
+            (Expr::Const(0), other) | (other, Expr::Const(0)) => other,
+            (a, b) => Expr::Add(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    pub fn product(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a.wrapping_mul(b)),
+            (Expr::Const(0), _) | (_, Expr::Const(0)) => Expr::Const(0),
+            (Expr::Const(1), other) | (other, Expr::Const(1)) => other,
+            (a, b) => Expr::Mul(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    pub fn less_than(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const((a < b) as i64),
+            (a, b) => Expr::LessThan(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    pub fn equals(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const((a == b) as i64),
+            (a, b) => Expr::Equals(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    /// A known `address` must not be negative, see `State::param`.
+    fn load(address: Expr, memory: &[Expr]) -> Expr {
+        match address {
+            Expr::Const(a) => memory.get(a as usize).cloned().unwrap_or(Expr::Const(0)),
+            address => Expr::Load(Rc::new(address), Rc::new(memory.to_vec())),
+        }
+    }
+
+    /// Evaluates the expression with the given values for the unknowns. Returns `None` if it
+    /// reads from a negative address.
+    pub fn eval(&self, values: &[i64]) -> Option<i64> {
+        Some(match self {
+            Expr::Const(c) => *c,
+            Expr::Var(v) => values[*v],
+            Expr::Add(a, b) => a.eval(values)?.wrapping_add(b.eval(values)?),
+            Expr::Mul(a, b) => a.eval(values)?.wrapping_mul(b.eval(values)?),
+            Expr::LessThan(a, b) => (a.eval(values)? < b.eval(values)?) as i64,
+            Expr::Equals(a, b) => (a.eval(values)? == b.eval(values)?) as i64,
+            Expr::Load(address, memory) => {
+                let address = address.eval(values)?;
+                if address < 0 {
+                    return None;
+                }
+                match memory.get(address as usize) {
+                    Some(expr) => expr.eval(values)?,
+                    None => 0,
+                }
+            }
+        })
+    }
+
+    /// Returns the expression as a linear combination of the unknowns, if it is one.
+    pub fn linear(&self) -> Option<Linear> {
+        match self {
+            Expr::Const(c) => Some(Linear {
+                constant: *c,
+                coefficients: BTreeMap::new(),
+            }),
+            Expr::Var(v) => {
+                let mut coefficients = BTreeMap::new();
+                coefficients.insert(*v, 1);
+                Some(Linear {
+                    constant: 0,
+                    coefficients,
+                })
+            }
+            Expr::Add(a, b) => {
+                let mut result = a.linear()?;
+                let b = b.linear()?;
+                result.constant = result.constant.checked_add(b.constant)?;
+                for (var, coefficient) in b.coefficients {
+                    let c = result.coefficients.entry(var).or_insert(0);
+                    *c = c.checked_add(coefficient)?;
+                }
+                result.coefficients.retain(|_, c| *c != 0);
+                Some(result)
+            }
+            Expr::Mul(a, b) => {
+                let a = a.linear()?;
+                let b = b.linear()?;
+                let (factor, mut result) = if a.coefficients.is_empty() {
+                    (a.constant, b)
+                } else if b.coefficients.is_empty() {
+                    (b.constant, a)
+                } else {
+                    return None;
+                };
+                result.constant = result.constant.checked_mul(factor)?;
+                for c in result.coefficients.values_mut() {
+                    *c = c.checked_mul(factor)?;
+                }
+                result.coefficients.retain(|_, c| *c != 0);
+                Some(result)
+            }
+            Expr::LessThan(..) | Expr::Equals(..) | Expr::Load(..) => None,
+        }
+    }
+
+    fn collect_vars(&self, vars: &mut Vec<usize>) {
+        match self {
+            Expr::Const(_) => {}
+            Expr::Var(v) => {
+                if !vars.contains(v) {
+                    vars.push(*v);
+                }
+            }
+            Expr::Add(a, b) | Expr::Mul(a, b) | Expr::LessThan(a, b) | Expr::Equals(a, b) => {
+                a.collect_vars(vars);
+                b.collect_vars(vars);
+            }
+            Expr::Load(address, memory) => {
+                address.collect_vars(vars);
+                for expr in memory.iter() {
+                    expr.collect_vars(vars);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Const(c) => write!(f, "{}", c),
+            Expr::Var(v) => write!(f, "x{}", v),
+            Expr::Add(a, b) => write!(f, "({} + {})", a, b),
+            Expr::Mul(a, b) => write!(f, "({} * {})", a, b),
+            Expr::LessThan(a, b) => write!(f, "({} < {})", a, b),
+            Expr::Equals(a, b) => write!(f, "({} == {})", a, b),
+            Expr::Load(address, _) => write!(f, "mem[{}]", address),
+        }
+    }
+}
+
+/// A branch condition that has to hold for a path to be taken.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Constraint {
+    pub expr: Expr,
+    pub non_zero: bool,
+}
+
+impl Constraint {
+    fn holds(&self, values: &[i64]) -> bool {
+        match self.expr.eval(values) {
+            Some(value) => (value != 0) == self.non_zero,
+            None => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum End {
+    Halt,
+    NeedInput,
+    StepLimit,
+    /// The program did something we can't follow symbolically, e.g. jump to an unknown address
+    Unsupported {
+        ip: i64,
+        reason: String,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Unknown `var` is used, but there are only domains for the first `domains` unknowns
+    MissingDomain { var: usize, domains: usize },
+}
+
+#[derive(Clone, Debug)]
+pub struct Path {
+    pub constraints: Vec<Constraint>,
+    pub outputs: Vec<Expr>,
+    pub memory: Vec<Expr>,
+    pub end: End,
+}
+
+impl Path {
+    /// Finds values for the unknowns so that `expr` evaluates to `target` and the constraints of
+    /// the path hold. `domains` are the allowed values for each unknown, by index.
+    ///
+    /// If `expr` is linear, one unknown is solved for directly and only the others are tried,
+    /// otherwise all combinations are tried. Returns `None` if there are more than `limit`
+    /// combinations to try or no solution was found.
+    pub fn solve(
+        &self,
+        expr: &Expr,
+        target: i64,
+        domains: &[RangeInclusive<i64>],
+        limit: u64,
+    ) -> core::result::Result<Option<Vec<i64>>, Error> {
+        let mut vars = Vec::new();
+        expr.collect_vars(&mut vars);
+        for constraint in &self.constraints {
+            constraint.expr.collect_vars(&mut vars);
+        }
+        if let Some(&var) = vars.iter().find(|&&var| var >= domains.len()) {
+            return Err(Error::MissingDomain {
+                var,
+                domains: domains.len(),
+            });
+        }
+
+        let linear = expr.linear();
+        // Solve for the variable with the biggest coefficient, so we try the fewest values
+        let solved = linear.as_ref().and_then(|linear| {
+            linear
+                .coefficients
+                .iter()
+                .max_by_key(|(_, c)| c.abs())
+                .map(|(&var, &c)| (var, c))
+        });
+        let enumerated: Vec<usize> = vars
+            .iter()
+            .cloned()
+            .filter(|&v| Some(v) != solved.map(|(var, _)| var))
+            .collect();
+
+        let combinations = enumerated.iter().try_fold(1u64, |acc, &v| {
+            let domain = &domains[v];
+            let size = (*domain.end() as i128 - *domain.start() as i128 + 1).max(0) as u64;
+            acc.checked_mul(size)
+        });
+        match combinations {
+            Some(combinations) if combinations <= limit => {}
+            _ => return Ok(None),
+        }
+
+        let mut values = vec![0; domains.len()];
+        let mut found = None;
+        for_each_combination(&enumerated, domains, &mut values, &mut |values| {
+            if let (Some(linear), Some((var, c))) = (&linear, solved) {
+                let rest = linear
+                    .coefficients
+                    .iter()
+                    .filter(|(&v, _)| v != var)
+                    .map(|(&v, &c)| c as i128 * values[v] as i128)
+                    .sum::<i128>();
+                let remaining = target as i128 - linear.constant as i128 - rest;
+                if remaining % c as i128 != 0 {
+                    return false;
+                }
+                let value = remaining / c as i128;
+                if value < *domains[var].start() as i128 || value > *domains[var].end() as i128 {
+                    return false;
+                }
+                values[var] = value as i64;
+            }
+            if expr.eval(values) == Some(target) && self.constraints.iter().all(|c| c.holds(values))
+            {
+                found = Some(values.to_vec());
+                true
+            } else {
+                false
+            }
+        });
+        Ok(found)
+    }
+}
+
+/// Calls `f` with every combination of values for `vars`, until it returns true.
+fn for_each_combination(
+    vars: &[usize],
+    domains: &[RangeInclusive<i64>],
+    values: &mut [i64],
+    f: &mut dyn FnMut(&mut [i64]) -> bool,
+) -> bool {
+    match vars.split_first() {
+        None => f(values),
+        Some((&var, rest)) => {
+            for value in domains[var].clone() {
+                values[var] = value;
+                if for_each_combination(rest, domains, values, f) {
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+#[derive(Clone)]
+struct State {
+    memory: Vec<Expr>,
+    ip: i64,
+    relative_base: i64,
+    inputs: VecDeque<Expr>,
+    constraints: Vec<Constraint>,
+    outputs: Vec<Expr>,
+    steps: usize,
+}
+
+enum Step {
+    Continue,
+    Fork(Expr, i64),
+    End(End),
+}
+
+pub struct Symbolic {
+    initial: State,
+    vars: usize,
+    max_steps: usize,
+    max_paths: usize,
+}
+
+impl Symbolic {
+    pub fn new(code: &Intcode) -> Self {
+        Symbolic {
+            initial: State {
+                memory: code.prog.iter().map(|&v| Expr::Const(v)).collect(),
+                ip: code.ip,
+                relative_base: code.relative_base,
+                inputs: code.inputs.iter().map(|&v| Expr::Const(v)).collect(),
+                constraints: Vec::new(),
+                outputs: Vec::new(),
+                steps: 0,
+            },
+            vars: 0,
+            max_steps: 1_000_000,
+            max_paths: 1000,
+        }
+    }
+
+    /// Replaces the value at `address` with a new unknown and returns its index.
+    pub fn unknown_cell(&mut self, address: usize) -> usize {
+        let var = self.next_var();
+        let memory = &mut self.initial.memory;
+        if address >= memory.len() {
+            memory.resize(address + 1, Expr::Const(0));
+        }
+        memory[address] = Expr::Var(var);
+        var
+    }
+
+    /// Adds a new unknown as the next input and returns its index.
+    pub fn unknown_input(&mut self) -> usize {
+        let var = self.next_var();
+        self.initial.inputs.push_back(Expr::Var(var));
+        var
+    }
+
+    pub fn add_input(&mut self, input: i64) -> &mut Self {
+        self.initial.inputs.push_back(Expr::Const(input));
+        self
+    }
+
+    pub fn max_steps(&mut self, max_steps: usize) -> &mut Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn max_paths(&mut self, max_paths: usize) -> &mut Self {
+        self.max_paths = max_paths;
+        self
+    }
+
+    fn next_var(&mut self) -> usize {
+        self.vars += 1;
+        self.vars - 1
+    }
+
+    /// Runs the program, forking whenever a jump depends on an unknown, until every path ended
+    /// or `max_paths` were found.
+    pub fn explore(&self) -> Vec<Path> {
+        let mut paths = Vec::new();
+        let mut pending = vec![self.initial.clone()];
+        while let Some(mut state) = pending.pop() {
+            if paths.len() >= self.max_paths {
+                break;
+            }
+            let end = loop {
+                if state.steps >= self.max_steps {
+                    break End::StepLimit;
+                }
+                state.steps += 1;
+                match state.step() {
+                    Step::Continue => {}
+                    Step::Fork(condition, target) => {
+                        let mut taken = state.clone();
+                        taken.constraints.push(Constraint {
+                            expr: condition.clone(),
+                            non_zero: true,
+                        });
+                        taken.ip = target;
+                        pending.push(taken);
+                        state.constraints.push(Constraint {
+                            expr: condition,
+                            non_zero: false,
+                        });
+                        state.ip += 3;
+                    }
+                    Step::End(end) => break end,
+                }
+            };
+            paths.push(Path {
+                constraints: state.constraints,
+                outputs: state.outputs,
+                memory: state.memory,
+                end,
+            });
+        }
+        paths
+    }
+}
+
+impl State {
+    fn get(&self, p: i64) -> Expr {
+        self.memory
+            .get(p as usize)
+            .cloned()
+            .unwrap_or(Expr::Const(0))
+    }
+
+    fn param(&self, p: i64, mode: i64) -> core::result::Result<Expr, End> {
+        let val = self.get(p);
+        let address = match mode {
+            0 => val,
+            1 => return Ok(val),
+            2 => Expr::sum(Expr::Const(self.relative_base), val),
+            _ => return Err(self.unsupported(&format!("unknown mode {} for load", mode))),
+        };
+        match address {
+            Expr::Const(a) if a < 0 => Err(self.unsupported("load from negative address")),
+            address => Ok(Expr::load(address, &self.memory)),
+        }
+    }
+
+    fn store(&mut self, p: i64, mode: i64, result: Expr) -> core::result::Result<(), End> {
+        let address = match (mode, self.get(p)) {
+            (0, Expr::Const(a)) => a,
+            (2, Expr::Const(a)) => self.relative_base + a,
+            (0, _) | (2, _) => return Err(self.unsupported("store to unknown address")),
+            (mode, _) => return Err(self.unsupported(&format!("unknown mode {} for store", mode))),
+        };
+        if address < 0 {
+            return Err(self.unsupported("store to negative address"));
+        }
+        let needed_size = address as usize + 1;
+        if needed_size > self.memory.len() {
+            self.memory.resize(needed_size, Expr::Const(0));
+        }
+        self.memory[address as usize] = result;
+        Ok(())
+    }
+
+    fn unsupported(&self, reason: &str) -> End {
+        End::Unsupported {
+            ip: self.ip,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn step(&mut self) -> Step {
+        self.execute().unwrap_or_else(Step::End)
+    }
+
+    fn execute(&mut self) -> core::result::Result<Step, End> {
+        if self.ip < 0 {
+            return Err(self.unsupported("jump to negative address"));
+        }
+        let instruction = match self.get(self.ip) {
+            Expr::Const(i) => i,
+            _ => return Err(self.unsupported("unknown instruction")),
+        };
+        let Instruction {
+            opcode: op,
+            modes: [mode1, mode2, mode3],
+        } = Instruction::decode(instruction);
+
+        match op {
+            99 => return Err(End::Halt),
+            1 | 2 | 7 | 8 => {
+                let a = self.param(self.ip + 1, mode1)?;
+                let b = self.param(self.ip + 2, mode2)?;
+                let result = match op {
+                    1 => Expr::sum(a, b),
+                    2 => Expr::product(a, b),
+                    7 => Expr::less_than(a, b),
+                    _ => Expr::equals(a, b),
+                };
+                self.store(self.ip + 3, mode3, result)?;
+                self.ip += 4;
+            }
+            3 => {
+                let input = self.inputs.pop_front().ok_or(End::NeedInput)?;
+                self.store(self.ip + 1, mode1, input)?;
+                self.ip += 2;
+            }
+            4 => {
+                let a = self.param(self.ip + 1, mode1)?;
+                self.outputs.push(a);
+                self.ip += 2;
+            }
+            5 | 6 => {
+                let a = self.param(self.ip + 1, mode1)?;
+                let b = self.param(self.ip + 2, mode2)?;
+                let condition = if op == 5 {
+                    a
+                } else {
+                    Expr::equals(a, Expr::Const(0))
+                };
+                let target = match b {
+                    Expr::Const(b) => b,
+                    _ => return Err(self.unsupported("jump to unknown address")),
+                };
+                match condition {
+                    Expr::Const(0) => self.ip += 3,
+                    Expr::Const(_) => self.ip = target,
+                    condition => return Ok(Step::Fork(condition, target)),
+                }
+            }
+            9 => match self.param(self.ip + 1, mode1)? {
+                Expr::Const(a) => {
+                    self.relative_base += a;
+                    self.ip += 2;
+                }
+                _ => return Err(self.unsupported("unknown relative base adjustment")),
+            },
+            _ => return Err(self.unsupported("unknown opcode")),
+        }
+        Ok(Step::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day2_input() {
+        let input = include_str!("../input/2019/day02.txt");
+        let mut symbolic = Symbolic::new(&Intcode::parse(input));
+        let noun = symbolic.unknown_cell(1);
+        let verb = symbolic.unknown_cell(2);
+
+        let paths = symbolic.explore();
+        assert_eq!(paths.len(), 1);
+        let path = &paths[0];
+        assert_eq!(path.end, End::Halt);
+
+        let result = &path.memory[0];
+        assert!(result.linear().is_some());
+        let values = path
+            .solve(result, 19_690_720, &[0..=99, 0..=99], 10_000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(100 * values[noun] + values[verb], 6718);
+    }
+
+    #[test]
+    fn test_input_equals() {
+        // Outputs 1 if the input is equal to 8
+        let mut symbolic = Symbolic::new(&Intcode::parse("3,9,8,9,10,9,4,9,99,-1,8"));
+        let input = symbolic.unknown_input();
+
+        let paths = symbolic.explore();
+        assert_eq!(paths.len(), 1);
+        let output = &paths[0].outputs[0];
+        assert_eq!(output.to_string(), "(x0 == 8)");
+        let values = paths[0].solve(output, 1, &[-100..=100], 1000).unwrap();
+        assert_eq!(values.unwrap()[input], 8);
+
+        assert_eq!(
+            paths[0].solve(output, 1, &[], 1000),
+            Err(Error::MissingDomain { var: 0, domains: 0 })
+        );
+    }
+
+    #[test]
+    fn test_fork_on_jump() {
+        // Outputs 0 if the input was zero or 1 if the input was non-zero
+        let mut symbolic = Symbolic::new(&Intcode::parse("3,3,1105,-1,9,1101,0,0,12,4,12,99,1"));
+        symbolic.unknown_input();
+
+        let paths = symbolic.explore();
+        assert_eq!(paths.len(), 2);
+        for path in &paths {
+            assert_eq!(path.end, End::Halt);
+            assert_eq!(path.constraints.len(), 1);
+            let expected = if path.constraints[0].non_zero { 1 } else { 0 };
+            assert_eq!(path.outputs, vec![Expr::Const(expected)]);
+        }
+        let zero = paths.iter().find(|p| !p.constraints[0].non_zero).unwrap();
+        let values = zero.solve(&Expr::Const(0), 0, &[-5..=5], 100).unwrap();
+        assert_eq!(values, Some(vec![0]));
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let reason = |prog: &str| {
+            let paths = Symbolic::new(&Intcode::parse(prog)).explore();
+            match &paths[0].end {
+                End::Unsupported { ip, reason } => (*ip, reason.clone()),
+                end => panic!("Expected unsupported, got {:?}", end),
+            }
+        };
+        assert_eq!(
+            reason("4,-1,99"),
+            (0, "load from negative address".to_string())
+        );
+        assert_eq!(
+            reason("109,-5,204,0,99"),
+            (2, "load from negative address".to_string())
+        );
+        assert_eq!(
+            reason("1101,1,1,-1,99"),
+            (0, "store to negative address".to_string())
+        );
+        assert_eq!(
+            reason("1105,1,-3"),
+            (-3, "jump to negative address".to_string())
+        );
+        assert_eq!(
+            reason("304,0,99"),
+            (0, "unknown mode 3 for load".to_string())
+        );
+        assert_eq!(
+            reason("11101,1,1,0,99"),
+            (0, "unknown mode 1 for store".to_string())
+        );
+    }
+}
@@ -0,0 +1,472 @@
+//! Taint tracking: which inputs each output depends on, see [`Intcode::track_taint`].
+//!
+//! Each input the program consumes gets a label, its index in the order they were consumed.
+//! Memory cells, the relative base and outputs have the set of labels of the values they were
+//! computed from:
+//!
+//! * Arithmetic and comparisons combine the labels of their operands.
+//! * Reading memory adds the labels of the address, writing to a tainted address taints the
+//!   value written. So a lookup in a table indexed by an input depends on the input.
+//! * A jump that depends on an input (the condition or the target) taints everything the program
+//!   writes or outputs until its branches join again, at the jump's immediate post-dominator in
+//!   the control flow graph of the program when tracking started (see
+//!   [`crate::optimize::joins`]), with the same relative base. If the branches only join when
+//!   the program halts or the function returns, or after the program wrote to its own
+//!   instructions, the labels stay until the program halts. Branches are assumed to leave the
+//!   relative base as they found it (e.g. by returning from the functions they call), so it gets
+//!   its labels from before the jump back.
+//!
+//! Instructions added with [`Intcode::register_opcode`] aren't tracked, and neither are writes
+//! to [`Intcode::prog`] from outside the program. Values that don't fit in an `i64` aren't
+//! followed as addresses.
+
+use crate::optimize;
+use crate::{Instruction, Intcode, Result, Word};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Labels of the inputs that a value depends on.
+pub type Labels = BTreeSet<usize>;
+
+#[derive(Clone, Debug)]
+pub struct Taint {
+    /// Labels of memory cells, only for the ones that have any
+    memory: BTreeMap<i64, Tainted>,
+    relative_base: Tainted,
+    /// Each set of labels of the jumps in effect so far, once. Values store the index of the one
+    /// when they were written instead of copying it. The first one is empty.
+    contexts: Vec<Labels>,
+    context_indices: BTreeMap<Labels, usize>,
+    /// Index of the jumps in effect now in `contexts`
+    context: usize,
+    scopes: Vec<Scope>,
+    /// Jumps and where their branches join, see [`optimize::joins`]
+    joins: BTreeMap<usize, usize>,
+    /// Cells that `joins` depends on
+    graph_cells: BTreeSet<usize>,
+    inputs: usize,
+    outputs: Vec<Tainted>,
+}
+
+/// The labels of a value: `data`, and the labels of [`Taint::contexts`] at index `control`.
+#[derive(Clone, Debug, Default)]
+struct Tainted {
+    data: Labels,
+    control: usize,
+}
+
+/// A jump that depends on inputs, until its branches join again.
+#[derive(Clone, Debug)]
+struct Scope {
+    /// `None` if the branches don't join before the program halts
+    join: Option<i64>,
+    relative_base: i64,
+    /// Labels of the relative base before, which it gets back when the branches join
+    relative_base_labels: Tainted,
+    /// Index of the jumps in effect before in [`Taint::contexts`]
+    context: usize,
+}
+
+/// What an instruction does to the labels, worked out before it runs because it can overwrite
+/// its own parameters.
+enum Effect {
+    Write(i64, Tainted),
+    Input(i64, Tainted),
+    Output(Tainted),
+    Jump(i64, Tainted),
+    RelativeBase(Tainted),
+    Nothing,
+}
+
+impl Taint {
+    pub(crate) fn new<W: Word>(code: &Intcode<W>) -> Self {
+        // Programs with cells that don't fit in an i64 can't be analyzed
+        let prog: Option<Vec<i64>> = code.prog.iter().map(|value| value.to_i64()).collect();
+        let (joins, graph_cells) = match (prog, code.ip()) {
+            (Some(prog), ip) if ip >= 0 => optimize::joins(&prog, ip as usize),
+            _ => Default::default(),
+        };
+        let mut context_indices = BTreeMap::new();
+        context_indices.insert(Labels::new(), 0);
+        Taint {
+            memory: BTreeMap::new(),
+            relative_base: Tainted::default(),
+            contexts: vec![Labels::new()],
+            context_indices,
+            context: 0,
+            scopes: Vec::new(),
+            joins,
+            graph_cells,
+            inputs: 0,
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Labels of each output so far, in order. Use [`Taint::output`] for a single one, as each
+    /// includes the control labels at the time.
+    pub fn outputs(&self) -> impl Iterator<Item = Labels> + '_ {
+        self.outputs.iter().map(move |output| self.labels(output))
+    }
+
+    /// Labels of output `index`, counting from 0.
+    pub fn output(&self, index: usize) -> Option<Labels> {
+        self.outputs.get(index).map(|output| self.labels(output))
+    }
+
+    /// Labels of the memory cell at `address`.
+    pub fn memory(&self, address: i64) -> Labels {
+        self.memory
+            .get(&address)
+            .map(|cell| self.labels(cell))
+            .unwrap_or_default()
+    }
+
+    pub fn relative_base(&self) -> Labels {
+        self.labels(&self.relative_base)
+    }
+
+    /// Labels of the jumps taken or not taken whose branches haven't joined yet, which everything
+    /// written or output now depends on.
+    pub fn control(&self) -> &Labels {
+        &self.contexts[self.context]
+    }
+
+    /// Number of inputs consumed so far, which is the label of the next one.
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    fn labels(&self, tainted: &Tainted) -> Labels {
+        let mut labels = tainted.data.clone();
+        labels.extend(self.contexts[tainted.control].iter().copied());
+        labels
+    }
+
+    fn cell(&self, address: i64) -> Option<&Tainted> {
+        self.memory.get(&address)
+    }
+
+    /// Adds the labels of `other` to `tainted`.
+    fn extend(&self, tainted: &mut Tainted, other: &Tainted) {
+        tainted.data.extend(other.data.iter().copied());
+        if other.control != tainted.control {
+            if tainted.control == 0 {
+                tainted.control = other.control;
+            } else {
+                tainted
+                    .data
+                    .extend(self.contexts[other.control].iter().copied());
+            }
+        }
+    }
+
+    fn effect<W: Word>(&self, code: &Intcode<W>) -> Effect {
+        let ip = code.ip();
+        let Instruction { opcode, modes } = match value(code, ip) {
+            Some(instruction) => Instruction::decode(instruction),
+            None => return Effect::Nothing,
+        };
+        let mut tainted = self.cell(ip).cloned().unwrap_or_default();
+        let mut read = |i: usize| {
+            let address = self.address(code, ip + i as i64, modes[i - 1], &mut tainted);
+            if let Some(cell) = address.and_then(|address| self.cell(address)) {
+                self.extend(&mut tainted, cell);
+            }
+        };
+        match opcode {
+            1 | 2 | 7 | 8 => {
+                read(1);
+                read(2);
+                match self.address(code, ip + 3, modes[2], &mut tainted) {
+                    Some(address) => Effect::Write(address, tainted),
+                    None => Effect::Nothing,
+                }
+            }
+            3 => match self.address(code, ip + 1, modes[0], &mut tainted) {
+                Some(address) => Effect::Input(address, tainted),
+                None => Effect::Nothing,
+            },
+            4 => {
+                read(1);
+                Effect::Output(tainted)
+            }
+            5 | 6 => {
+                read(1);
+                read(2);
+                Effect::Jump(ip, tainted)
+            }
+            9 => {
+                read(1);
+                Effect::RelativeBase(tainted)
+            }
+            _ => Effect::Nothing,
+        }
+    }
+
+    /// Returns the address that parameter cell `param` refers to (`None` for immediate mode, or if
+    /// it doesn't fit), and adds the labels of computing it to `tainted`.
+    fn address<W: Word>(
+        &self,
+        code: &Intcode<W>,
+        param: i64,
+        mode: i64,
+        tainted: &mut Tainted,
+    ) -> Option<i64> {
+        if let Some(cell) = self.cell(param) {
+            self.extend(tainted, cell);
+        }
+        match mode {
+            0 => value(code, param),
+            2 => {
+                self.extend(tainted, &self.relative_base);
+                value(code, param)?.checked_add(code.relative_base())
+            }
+            _ => None,
+        }
+    }
+
+    fn apply(&mut self, effect: Effect, relative_base: i64) {
+        match effect {
+            Effect::Write(address, tainted) => self.write(address, tainted),
+            Effect::Input(address, mut tainted) => {
+                tainted.data.insert(self.inputs);
+                self.inputs += 1;
+                self.write(address, tainted);
+            }
+            Effect::Output(tainted) => {
+                let output = self.current(tainted);
+                self.outputs.push(output);
+            }
+            Effect::Jump(ip, tainted) => {
+                let labels = self.current(tainted).data;
+                if !labels.is_empty() {
+                    let join = self.joins.get(&(ip as usize)).map(|&join| join as i64);
+                    self.enter(labels, join, relative_base);
+                }
+            }
+            Effect::RelativeBase(tainted) => {
+                let mut relative_base = self.relative_base.clone();
+                self.extend(&mut relative_base, &tainted);
+                self.relative_base = self.current(relative_base);
+            }
+            Effect::Nothing => {}
+        }
+    }
+
+    fn write(&mut self, address: i64, tainted: Tainted) {
+        if self.graph_cells.contains(&(address as usize)) {
+            // The control flow graph might not hold anymore
+            self.joins.clear();
+            self.graph_cells.clear();
+            for scope in &mut self.scopes {
+                scope.join = None;
+            }
+        }
+        let tainted = self.current(tainted);
+        if tainted.data.is_empty() && tainted.control == 0 {
+            self.memory.remove(&address);
+        } else {
+            self.memory.insert(address, tainted);
+        }
+    }
+
+    /// Adds the labels of the jumps in effect to a value that is written or output now.
+    fn current(&self, mut tainted: Tainted) -> Tainted {
+        if tainted.control != self.context {
+            let control = &self.contexts[tainted.control];
+            tainted.data.extend(control.iter().copied());
+        }
+        let context = &self.contexts[self.context];
+        tainted.data.retain(|label| !context.contains(label));
+        tainted.control = self.context;
+        tainted
+    }
+
+    /// Puts `labels` in effect until the program gets to `join` with `relative_base`. A jump
+    /// that runs again before that (e.g. in a loop) doesn't need another scope.
+    fn enter(&mut self, labels: Labels, join: Option<i64>, relative_base: i64) {
+        let mut context = self.contexts[self.context].clone();
+        context.extend(labels);
+        let context = match self.context_indices.get(&context) {
+            Some(&index) => index,
+            None => {
+                self.contexts.push(context.clone());
+                self.context_indices
+                    .insert(context, self.contexts.len() - 1);
+                self.contexts.len() - 1
+            }
+        };
+        let same = |scope: &Scope| scope.join == join && scope.relative_base == relative_base;
+        if !self.scopes.last().is_some_and(same) {
+            self.scopes.push(Scope {
+                join,
+                relative_base,
+                relative_base_labels: self.relative_base.clone(),
+                context: self.context,
+            });
+        }
+        self.context = context;
+    }
+
+    /// Ends the scopes of the jumps whose branches join at `ip`, and the ones inside them.
+    fn leave(&mut self, ip: i64, relative_base: i64) {
+        let joined = self
+            .scopes
+            .iter()
+            .position(|scope| scope.join == Some(ip) && scope.relative_base == relative_base);
+        if let Some(i) = joined {
+            self.scopes.truncate(i + 1);
+            let scope = self.scopes.pop().unwrap();
+            self.context = scope.context;
+            self.relative_base = scope.relative_base_labels;
+        }
+    }
+}
+
+/// Runs one instruction of a machine with taint tracking enabled.
+pub(crate) fn step<W: Word>(code: &mut Intcode<W>) -> Option<Result<W>> {
+    let mut taint = code.taint.take()?;
+    let effect = taint.effect(code);
+    let relative_base = code.relative_base();
+    let result = code.step();
+    match result {
+        Some(Result::NeedInput) | Some(Result::Error(_)) => {}
+        _ => {
+            taint.apply(effect, relative_base);
+            if !taint.scopes.is_empty() {
+                taint.leave(code.ip(), code.relative_base());
+            }
+        }
+    }
+    code.taint = Some(taint);
+    result
+}
+
+/// The value of the cell at `address`, `None` if it doesn't fit in an `i64`. Cells beyond the
+/// memory are 0.
+fn value<W: Word>(code: &Intcode<W>, address: i64) -> Option<i64> {
+    if address < 0 {
+        return None;
+    }
+    match code.prog.get(address as usize) {
+        Some(value) => value.to_i64(),
+        None => Some(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+
+    fn labels(labels: &[usize]) -> Labels {
+        labels.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_data() {
+        // Reads a, b and c, outputs a, a + b, 7 and c
+        let mut code = Intcode::parse("3,100,3,101,3,102,4,100,1,100,101,103,4,103,104,7,4,102,99");
+        code.track_taint();
+        code.add_input(1).add_input(2).add_input(3);
+        assert_eq!(code.run_all(), vec![1, 3, 7, 3]);
+        let taint = code.taint().unwrap();
+        assert_eq!(
+            taint.outputs().collect::<Vec<_>>(),
+            vec![labels(&[0]), labels(&[0, 1]), labels(&[]), labels(&[2])]
+        );
+        assert_eq!(taint.memory(103), labels(&[0, 1]));
+        assert_eq!(taint.inputs(), 3);
+
+        // Outputs the cell at the address from the input, using the relative base
+        let mut code = Intcode::parse("3,7,9,7,204,0,99,0");
+        code.track_taint();
+        code.add_input(4);
+        assert_eq!(code.run_all(), vec![204]);
+        assert_eq!(
+            code.taint().unwrap().outputs().collect::<Vec<_>>(),
+            vec![labels(&[0])]
+        );
+    }
+
+    #[test]
+    fn test_control() {
+        let program = compile(
+            "
+            fn main() {
+                let a = input();
+                let b = input();
+                output(5);
+                if a == 0 {
+                    output(1);
+                } else {
+                    output(2);
+                }
+                output(b * 0);
+            }
+            ",
+        )
+        .unwrap();
+        let mut code = Intcode::new(program);
+        code.track_taint();
+        code.add_input(0).add_input(4);
+        assert_eq!(code.run_all(), vec![5, 1, 0]);
+        let taint = code.taint().unwrap();
+        assert_eq!(
+            taint.outputs().collect::<Vec<_>>(),
+            // The branches join before the last output
+            vec![labels(&[]), labels(&[0]), labels(&[1])]
+        );
+        assert!(taint.control().is_empty());
+    }
+
+    #[test]
+    fn test_day19() {
+        // Whether a point is in the beam depends on both coordinates
+        let mut code = Intcode::parse(include_str!("../input/2019/day19.txt"));
+        code.track_taint();
+        code.add_input(10).add_input(12);
+        code.run_expect_output();
+        assert_eq!(
+            code.taint().unwrap().outputs().collect::<Vec<_>>(),
+            vec![labels(&[0, 1])]
+        );
+    }
+
+    #[test]
+    fn test_day13() {
+        // Plays until the first points are scored. The first ones don't depend on any move, the
+        // next ones only on some: the branches on a move join again once the paddle moved.
+        let mut code = Intcode::parse(include_str!("../input/2019/day13.txt"));
+        code.prog[0] = 2;
+        code.track_taint();
+        let (mut ball, mut paddle): (i64, i64) = (0, 0);
+        let mut outputs = 0;
+        let mut scores = Vec::new();
+        while scores.len() < 2 {
+            match code.run() {
+                Result::NeedInput => {
+                    code.add_input((ball - paddle).signum());
+                }
+                Result::Output(x) => {
+                    let (y, id) = (code.run_expect_output(), code.run_expect_output());
+                    outputs += 3;
+                    match (x, y, id) {
+                        (-1, 0, _) => scores.push((id, outputs - 1)),
+                        (_, _, 3) => paddle = x,
+                        (_, _, 4) => ball = x,
+                        _ => {}
+                    }
+                }
+                result => panic!("Expected output or input, got {:?}", result),
+            }
+        }
+        let taint = code.taint().unwrap();
+        let (first, last) = (scores[0], scores[1]);
+        assert_eq!(first.0, 0);
+        assert_eq!(taint.output(first.1), Some(labels(&[])));
+        assert_eq!(taint.inputs(), 6);
+        assert_eq!(taint.output(last.1), Some(labels(&[0, 2])));
+    }
+}
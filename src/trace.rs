@@ -0,0 +1,368 @@
+//! Execution traces, and finding where two of them diverge, e.g. for two builds of the VM or for
+//! the VM and a reference interpreter such as `calculate` in day 5.
+//!
+//! A trace has a [`Step`] per instruction. [`record`] makes one with [`Intcode`], other
+//! implementations can write the text format, with a step per line:
+//!
+//! ```text
+//! ip=0 op=3 args=225 in=1 write=225:1
+//! ip=2 op=1 args=225,6,6 write=6:1101
+//! ip=6 op=1101 args=1,238,225 write=225:239
+//! ```
+//!
+//! `args` are the parameter cells as they are in memory, before applying the modes. `in`, `out`
+//! and `write` are only there for steps that have them. [`diff`] finds the first step where two
+//! traces differ.
+
+use crate::{Instruction, Intcode, Result};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Step {
+    pub ip: i64,
+    /// The instruction, including the modes
+    pub op: i64,
+    pub args: Vec<i64>,
+    pub input: Option<i64>,
+    pub output: Option<i64>,
+    /// Address and value of each memory write
+    pub writes: Vec<(i64, i64)>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ip={} op={}", self.ip, self.op)?;
+        if !self.args.is_empty() {
+            let args: Vec<String> = self.args.iter().map(|a| a.to_string()).collect();
+            write!(f, " args={}", args.join(","))?;
+        }
+        if let Some(input) = self.input {
+            write!(f, " in={}", input)?;
+        }
+        if let Some(output) = self.output {
+            write!(f, " out={}", output)?;
+        }
+        for (address, value) in &self.writes {
+            write!(f, " write={}:{}", address, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let number = |v: &str| {
+            v.parse::<i64>()
+                .map_err(|_| format!("Invalid number {:?}", v))
+        };
+        let mut step = Step::default();
+        let (mut ip, mut op) = (None, None);
+        for field in s.split_whitespace() {
+            let (key, value) = match field.find('=') {
+                Some(i) => (&field[..i], &field[i + 1..]),
+                None => return Err(format!("Expected key=value, got {:?}", field)),
+            };
+            match key {
+                "ip" => ip = Some(number(value)?),
+                "op" => op = Some(number(value)?),
+                "args" => {
+                    step.args = value
+                        .split(',')
+                        .map(number)
+                        .collect::<std::result::Result<_, _>>()?
+                }
+                "in" => step.input = Some(number(value)?),
+                "out" => step.output = Some(number(value)?),
+                "write" => match value.find(':') {
+                    Some(i) => step
+                        .writes
+                        .push((number(&value[..i])?, number(&value[i + 1..])?)),
+                    None => return Err(format!("Expected address:value, got {:?}", value)),
+                },
+                _ => return Err(format!("Unknown key {:?}", key)),
+            }
+        }
+        step.ip = ip.ok_or("Missing ip")?;
+        step.op = op.ok_or("Missing op")?;
+        Ok(step)
+    }
+}
+
+/// Parses a trace in the text format, one step per line.
+pub fn parse(text: &str) -> std::result::Result<Vec<Step>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| line.parse().map_err(|e| format!("Line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// Runs `code` for at most `limit` steps, until it halts, fails, loops (with
+/// [`Intcode::detect_loops`]) or needs more input than it was given. The step that halts, fails
+/// or completes the loop is included.
+pub fn record(mut code: Intcode, limit: usize) -> Vec<Step> {
+    let writes = Arc::new(Mutex::new(Vec::new()));
+    let input = Arc::new(Mutex::new(None));
+    let w = writes.clone();
+    code.on_write(move |address, value| w.lock().unwrap().push((address, *value)));
+    let i = input.clone();
+    code.on_input(move |value| *i.lock().unwrap() = Some(*value));
+
+    let mut trace = Vec::new();
+    while trace.len() < limit {
+        let ip = code.ip();
+        let op = code.prog.get(ip as usize).copied().unwrap_or(0);
+        let params = Instruction::decode(op).params().unwrap_or(0);
+        let args = (1..=params)
+            .map(|i| code.prog.get(ip as usize + i).copied().unwrap_or(0))
+            .collect();
+        let result = code.step();
+        if result == Some(Result::NeedInput) {
+            break;
+        }
+        trace.push(Step {
+            ip,
+            op,
+            args,
+            input: input.lock().unwrap().take(),
+            output: match result {
+                Some(Result::Output(o)) => Some(o),
+                _ => None,
+            },
+            writes: writes.lock().unwrap().drain(..).collect(),
+        });
+        if let Some(Result::Halt) | Some(Result::Error(_)) | Some(Result::Looping(_)) = result {
+            break;
+        }
+    }
+    trace
+}
+
+/// The first part of a step that differs, in the order they are compared.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    Ip,
+    Op,
+    Args,
+    Input,
+    Output,
+    Writes,
+    /// One trace ended before the other.
+    End,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Divergence<'a> {
+    /// Index of the first step that differs
+    pub index: usize,
+    pub field: Field,
+    /// Steps before the divergence, which are the same in both traces
+    pub before: &'a [Step],
+    /// Steps of each trace from the divergence on
+    pub left: &'a [Step],
+    pub right: &'a [Step],
+}
+
+impl fmt::Display for Divergence<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Diverges at step {} ({:?})", self.index, self.field)?;
+        let start = self.index - self.before.len();
+        for (i, step) in self.before.iter().enumerate() {
+            writeln!(f, "  {:>8}  {}", start + i, step)?;
+        }
+        for (side, steps) in &[("<", self.left), (">", self.right)] {
+            for (i, step) in steps.iter().enumerate() {
+                writeln!(f, "{} {:>8}  {}", side, self.index + i, step)?;
+            }
+            if steps.is_empty() {
+                writeln!(f, "{} {:>8}  (end of trace)", side, self.index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the first difference between traces `a` and `b`, with up to `context` steps before
+/// and after it.
+pub fn diff<'a>(a: &'a [Step], b: &'a [Step], context: usize) -> Option<Divergence<'a>> {
+    let (index, field) = a
+        .iter()
+        .zip(b)
+        .enumerate()
+        .find_map(|(i, (x, y))| compare(x, y).map(|field| (i, field)))
+        .or_else(|| {
+            let common = a.len().min(b.len());
+            if a.len() != b.len() {
+                Some((common, Field::End))
+            } else {
+                None
+            }
+        })?;
+    let after =
+        |steps: &'a [Step]| &steps[index.min(steps.len())..(index + context).min(steps.len())];
+    Some(Divergence {
+        index,
+        field,
+        before: &a[index.saturating_sub(context)..index],
+        left: after(a),
+        right: after(b),
+    })
+}
+
+fn compare(a: &Step, b: &Step) -> Option<Field> {
+    if a.ip != b.ip {
+        Some(Field::Ip)
+    } else if a.op != b.op {
+        Some(Field::Op)
+    } else if a.args != b.args {
+        Some(Field::Args)
+    } else if a.input != b.input {
+        Some(Field::Input)
+    } else if a.output != b.output {
+        Some(Field::Output)
+    } else if a.writes != b.writes {
+        Some(Field::Writes)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reference interpreter for day 5 that treats "equals" as "less than or equal".
+    fn buggy(mut prog: Vec<i64>, input: i64) -> Vec<Step> {
+        let mut trace = Vec::new();
+        let mut ip = 0;
+        loop {
+            let op = prog[ip];
+            let params = Instruction::decode(op).params().unwrap();
+            let at = ip;
+            let get = move |prog: &[i64], i: usize| {
+                let mode = op / 10i64.pow(i as u32 + 1) % 10;
+                if mode == 1 {
+                    prog[at + i]
+                } else {
+                    prog[prog[at + i] as usize]
+                }
+            };
+            let mut writes = Vec::new();
+            let mut set = |prog: &mut Vec<i64>, i: usize, value: i64| {
+                let address = prog[at + i];
+                prog[address as usize] = value;
+                writes.push((address, value));
+            };
+            let mut step = Step {
+                ip: at as i64,
+                op,
+                args: prog[at + 1..=at + params].to_vec(),
+                ..Step::default()
+            };
+            ip += 1 + params;
+            match op % 100 {
+                1 => {
+                    let value = get(&prog, 1) + get(&prog, 2);
+                    set(&mut prog, 3, value);
+                }
+                2 => {
+                    let value = get(&prog, 1) * get(&prog, 2);
+                    set(&mut prog, 3, value);
+                }
+                3 => {
+                    set(&mut prog, 1, input);
+                    step.input = Some(input);
+                }
+                4 => step.output = Some(get(&prog, 1)),
+                5 if get(&prog, 1) != 0 => ip = get(&prog, 2) as usize,
+                6 if get(&prog, 1) == 0 => ip = get(&prog, 2) as usize,
+                7 => {
+                    let value = (get(&prog, 1) < get(&prog, 2)) as i64;
+                    set(&mut prog, 3, value);
+                }
+                8 => {
+                    let value = (get(&prog, 1) <= get(&prog, 2)) as i64;
+                    set(&mut prog, 3, value);
+                }
+                _ => {}
+            }
+            step.writes = writes;
+            let halted = op == 99;
+            trace.push(step);
+            if halted {
+                return trace;
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut code = Intcode::parse(include_str!("../input/2019/day05.txt"));
+        let prog = code.prog.clone();
+        code.add_input(5);
+        let trace = record(code, 10_000);
+        assert_eq!(trace.last().map(|s| s.op), Some(99));
+        assert_eq!(diff(&trace, &trace, 3), None);
+
+        let reference = buggy(prog, 5);
+        let divergence = diff(&trace, &reference, 3).unwrap();
+        assert_eq!(divergence.field, Field::Writes);
+        assert_eq!(divergence.left[0].op % 100, 8);
+        assert_eq!(
+            divergence.before,
+            &trace[divergence.index - 3..divergence.index]
+        );
+        assert_eq!(divergence.left.len(), 3);
+
+        let shown = divergence.to_string();
+        assert!(shown.starts_with(&format!("Diverges at step {} (Writes)\n", divergence.index)));
+        assert_eq!(shown.lines().count(), 1 + 3 + 3 + 3);
+    }
+
+    #[test]
+    fn test_end() {
+        // Needs two inputs, but only gets one
+        let code = Intcode::parse("3,9,3,10,4,9,4,10,99,0,0");
+        let mut short = code.clone();
+        short.add_input(1);
+        let mut long = code;
+        long.add_input(1).add_input(2);
+        let (short, long) = (record(short, 100), record(long, 100));
+        assert_eq!(short.len(), 1);
+        let divergence = diff(&short, &long, 10).unwrap();
+        assert_eq!((divergence.index, divergence.field), (1, Field::End));
+        assert!(divergence
+            .to_string()
+            .contains("<        1  (end of trace)"));
+    }
+
+    #[test]
+    fn test_looping() {
+        // Counts down from 3, then jumps to itself
+        let mut code = Intcode::parse("1001,11,-1,11,1005,11,0,1105,1,7,99,3");
+        code.detect_loops();
+        let trace = record(code, 10_000);
+        assert!(trace.len() < 100, "{}", trace.len());
+        assert_eq!(trace.last().map(|s| s.ip), Some(7));
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut code = Intcode::parse(include_str!("../input/2019/day05.txt"));
+        code.add_input(1);
+        let trace = record(code, 10_000);
+        let text: String = trace.iter().map(|s| format!("{}\n", s)).collect();
+        assert!(text.starts_with("ip=0 op=3 args=225 in=1 write=225:1\n"));
+        assert_eq!(parse(&text), Ok(trace));
+
+        assert_eq!(
+            parse("ip=0 op=99\n\nip=1 op=1 args=1,x,3"),
+            Err("Line 3: Invalid number \"x\"".to_string())
+        );
+        assert_eq!(parse("op=99"), Err("Line 1: Missing ip".to_string()));
+    }
+}
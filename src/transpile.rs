@@ -0,0 +1,321 @@
+//! Ahead-of-time translation of Intcode programs to Rust.
+//!
+//! The generated module has a `Machine` with a `run` method that returns the same `Result` as
+//! `Intcode::run`, but every instruction is a match arm with the modes and parameters compiled
+//! in. Before running an arm, the machine checks that the instruction wasn't modified by the
+//! program (e.g. `prog[0] = 2` in day 13). If it was, or the program jumps to an address that
+//! wasn't compiled, that instruction is run by the interpreter instead.
+//!
+//! `cargo run --bin transpile <program> [crate]` prints the module for a program, see
+//! [`crate::compiled`] for the ones that are checked in.
+
+use crate::Instruction;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Returns the Rust source of a module for `prog`. `krate` is the path of this crate from the
+/// generated module, e.g. `crate` or `advent_of_code_2019`.
+pub fn transpile(prog: &[i64], krate: &str) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "//! Generated by `cargo run --bin transpile`, don't edit."
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#![allow(clippy::all, unreachable_code)]").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "use {}::{{Error, Intcode, Result}};", krate).unwrap();
+    writeln!(out).unwrap();
+
+    write!(out, "pub const PROG: &[i64] = &[").unwrap();
+    for (i, value) in prog.iter().enumerate() {
+        if i % 16 == 0 {
+            write!(out, "\n   ").unwrap();
+        }
+        write!(out, " {},", value).unwrap();
+    }
+    writeln!(out, "\n];").unwrap();
+
+    out.push_str(
+        r#"
+#[derive(Clone)]
+pub struct Machine {
+    pub code: Intcode,
+}
+
+impl Machine {
+    pub fn new() -> Self {
+        Self::with_code(Intcode::new(PROG.to_vec()))
+    }
+
+    /// Runs the compiled code on the state of `code`, which should be running the same program.
+    pub fn with_code(code: Intcode) -> Self {
+        Machine { code }
+    }
+
+    pub fn add_input(&mut self, input: i64) -> &mut Self {
+        self.code.add_input(input);
+        self
+    }
+
+    /// Runs one instruction in the interpreter.
+    fn interpret(&mut self, ip: &mut i64, rb: &mut i64) -> Option<Result> {
+        self.code.set_ip(*ip);
+        self.code.set_relative_base(*rb);
+        let result = self.code.step();
+        *ip = self.code.ip();
+        *rb = self.code.relative_base();
+        result
+    }
+
+    pub fn run(&mut self) -> Result {
+        let mut ip = self.code.ip();
+        let mut rb = self.code.relative_base();
+        let result = loop {
+            match ip {
+"#,
+    );
+
+    for ip in instructions(prog) {
+        let instruction = Instruction::decode(prog[ip]);
+        let params = instruction.params().unwrap();
+        let cells: Vec<String> = prog[ip..=ip + params]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        writeln!(out, "                {} => {{", ip).unwrap();
+        writeln!(
+            out,
+            "                    if self.code.prog[{}..={}] != [{}] {{",
+            ip,
+            ip + params,
+            cells.join(", ")
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "                        if let Some(result) = self.interpret(&mut ip, &mut rb) {{"
+        )
+        .unwrap();
+        writeln!(out, "                            break result;").unwrap();
+        writeln!(out, "                        }}").unwrap();
+        writeln!(out, "                        continue;").unwrap();
+        writeln!(out, "                    }}").unwrap();
+        for line in body(prog, ip, instruction) {
+            writeln!(out, "                    {}", line).unwrap();
+        }
+        writeln!(out, "                }}").unwrap();
+    }
+
+    out.push_str(
+        r#"                _ => {
+                    if let Some(result) = self.interpret(&mut ip, &mut rb) {
+                        break result;
+                    }
+                }
+            }
+        };
+        self.code.set_ip(ip);
+        self.code.set_relative_base(rb);
+        result
+    }
+}
+
+fn get(prog: &mut Vec<i64>, p: i64) -> i64 {
+    if let Some(&value) = prog.get(p as usize) {
+        return value;
+    }
+    prog.resize(p as usize + 1, 0);
+    0
+}
+
+fn set(prog: &mut Vec<i64>, p: i64, value: i64) {
+    if p as usize >= prog.len() {
+        prog.resize(p as usize + 1, 0);
+    }
+    prog[p as usize] = value;
+}
+"#,
+    );
+    out
+}
+
+/// Addresses to compile: everything reachable from the start or from an immediate that could be
+/// a jump target, e.g. a return address that is pushed on the stack.
+fn instructions(prog: &[i64]) -> BTreeSet<usize> {
+    let valid = |ip: usize| -> Option<(Instruction, usize)> {
+        let instruction = Instruction::decode(*prog.get(ip)?);
+        let params = instruction.params()?;
+        if ip + params >= prog.len() || instruction.modes.iter().any(|&m| m > 2) {
+            return None;
+        }
+        Some((instruction, params))
+    };
+
+    let mut pending = vec![0];
+    let mut seen = BTreeSet::new();
+    let mut result = BTreeSet::new();
+    while let Some(ip) = pending.pop() {
+        if !seen.insert(ip) {
+            continue;
+        }
+        let (instruction, params) = match valid(ip) {
+            Some(valid) => valid,
+            None => continue,
+        };
+        result.insert(ip);
+        for i in 0..params {
+            let value = prog[ip + 1 + i];
+            if instruction.modes[i] == 1 && value >= 0 && (value as usize) < prog.len() {
+                pending.push(value as usize);
+            }
+        }
+        match instruction.opcode {
+            99 => {}
+            5 | 6 => pending.push(ip + 3),
+            _ => pending.push(ip + params + 1),
+        }
+    }
+    result
+}
+
+fn body(prog: &[i64], ip: usize, instruction: Instruction) -> Vec<String> {
+    let read = |i: usize| -> String {
+        let value = prog[ip + 1 + i];
+        match instruction.modes[i] {
+            0 => format!("get(&mut self.code.prog, {})", value),
+            1 => value.to_string(),
+            _ => format!("get(&mut self.code.prog, rb + {})", value),
+        }
+    };
+    let write = |i: usize, value: &str| -> String {
+        let address = prog[ip + 1 + i];
+        match instruction.modes[i] {
+            0 => format!("set(&mut self.code.prog, {}, {});", address, value),
+            _ => format!("set(&mut self.code.prog, rb + {}, {});", address, value),
+        }
+    };
+    let ip = ip as i64;
+
+    match instruction.opcode {
+        99 => vec!["break Result::Halt;".to_string()],
+        1 | 2 | 7 | 8 => {
+            let mut lines = vec![
+                format!("let a = {};", read(0)),
+                format!("let b = {};", read(1)),
+            ];
+            match instruction.opcode {
+                1 | 2 => {
+                    let method = if instruction.opcode == 1 {
+                        "add"
+                    } else {
+                        "mul"
+                    };
+                    lines.push(format!(
+                        "let result = match self.code.overflow().{}(a, b) {{",
+                        method
+                    ));
+                    lines.push("    Some(result) => result,".to_string());
+                    lines.push(format!(
+                        "    None => break Result::Error(Error::Overflow {{ ip: {} }}),",
+                        ip
+                    ));
+                    lines.push("};".to_string());
+                    lines.push(write(2, "result"));
+                }
+                7 => lines.push(write(2, "(a < b) as i64")),
+                _ => lines.push(write(2, "(a == b) as i64")),
+            }
+            lines.push(format!("ip = {};", ip + 4));
+            lines
+        }
+        3 => vec![
+            "match self.code.next_input() {".to_string(),
+            format!("    Some(input) => {{ {} }}", write(0, "input")),
+            "    None => break Result::NeedInput,".to_string(),
+            "}".to_string(),
+            format!("ip = {};", ip + 2),
+        ],
+        4 => vec![
+            format!("let a = {};", read(0)),
+            format!("ip = {};", ip + 2),
+            "break Result::Output(a);".to_string(),
+        ],
+        5 | 6 => vec![
+            format!(
+                "if {} {} 0 {{",
+                read(0),
+                if instruction.opcode == 5 { "!=" } else { "==" }
+            ),
+            format!("    ip = {};", read(1)),
+            "} else {".to_string(),
+            format!("    ip = {};", ip + 3),
+            "}".to_string(),
+        ],
+        9 => vec![format!("rb += {};", read(0)), format!("ip = {};", ip + 2)],
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiled::{day13, day19};
+    use crate::{Intcode, Result};
+
+    #[test]
+    fn test_up_to_date() {
+        let input = include_str!("../input/2019/day13.txt");
+        let prog = Intcode::parse(input).prog;
+        assert_eq!(transpile(&prog, "crate"), include_str!("compiled/day13.rs"));
+
+        let input = include_str!("../input/2019/day19.txt");
+        let prog = Intcode::parse(input).prog;
+        assert_eq!(transpile(&prog, "crate"), include_str!("compiled/day19.rs"));
+    }
+
+    #[test]
+    fn test_day19() {
+        let mut count = 0;
+        for y in 0..50 {
+            for x in 0..50 {
+                let mut machine = day19::Machine::new();
+                machine.add_input(x).add_input(y);
+                match machine.run() {
+                    Result::Output(o) => count += o,
+                    other => panic!("Expected output, got {:?}", other),
+                }
+            }
+        }
+        assert_eq!(count, 110);
+    }
+
+    #[test]
+    fn test_day13() {
+        // Playing for free modifies the first instruction, which then has to be interpreted
+        let mut code = Intcode::parse(include_str!("../input/2019/day13.txt"));
+        code.prog[0] = 2;
+        let mut machine = day13::Machine::with_code(code.clone());
+
+        loop {
+            let result = code.run();
+            assert_eq!(machine.run(), result);
+            match result {
+                Result::Output(_) => {}
+                Result::NeedInput => {
+                    code.add_input(0);
+                    machine.add_input(0);
+                }
+                Result::Halt => break,
+                Result::Error(e) => panic!("{}", e),
+                Result::Looping(cycle) => panic!("Looping with a cycle of {} instructions", cycle),
+            }
+        }
+        assert_eq!(machine.code.prog, code.prog);
+    }
+}